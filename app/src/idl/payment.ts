@@ -14,97 +14,163 @@ export type Payment = {
   },
   "instructions": [
     {
-      "name": "changeFeeTo",
+      "name": "acceptEthSigner",
       "discriminator": [
-        4,
-        215,
-        177,
-        253,
-        175,
-        90,
-        205,
-        197
+        224,
+        51,
+        110,
+        200,
+        33,
+        220,
+        21,
+        50
       ],
       "accounts": [
         {
           "name": "paymentState",
-          "writable": true
-        },
-        {
-          "name": "currentOwner",
-          "signer": true
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "newOwner"
+          "name": "instructionSysvar"
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
     },
     {
-      "name": "changeOwner",
+      "name": "acceptFeeTo",
       "discriminator": [
-        109,
-        40,
-        40,
-        90,
-        224,
-        120,
-        193,
-        184
+        52,
+        86,
+        204,
+        192,
+        214,
+        247,
+        136,
+        211
       ],
       "accounts": [
         {
           "name": "paymentState",
-          "writable": true
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "currentOwner",
+          "name": "newOwner",
           "signer": true
-        },
-        {
-          "name": "newOwner"
         }
       ],
       "args": []
     },
     {
-      "name": "changeSigner",
+      "name": "acceptOwnership",
       "discriminator": [
-        178,
-        235,
-        108,
-        157,
-        105,
-        50,
-        210,
-        90
+        172,
+        23,
+        43,
+        13,
+        238,
+        213,
+        85,
+        150
       ],
       "accounts": [
         {
           "name": "paymentState",
-          "writable": true
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "currentOwner",
+          "name": "newOwner",
           "signer": true
-        },
-        {
-          "name": "newOwner"
         }
       ],
       "args": []
     },
     {
-      "name": "deposit",
+      "name": "acceptSigner",
       "discriminator": [
-        242,
-        35,
-        198,
+        12,
+        109,
         137,
-        82,
-        225,
-        242,
-        182
+        210,
+        5,
+        59,
+        147,
+        211
       ],
       "accounts": [
         {
@@ -134,63 +200,113 @@ export type Payment = {
           }
         },
         {
-          "name": "userTokenAccount",
+          "name": "newOwner",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "addQuorumSigner",
+      "discriminator": [
+        233,
+        75,
+        180,
+        62,
+        191,
+        127,
+        204,
+        24
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
+                  112,
+                  97,
+                  121,
+                  109,
                   101,
-                  114,
+                  110,
+                  116,
                   45,
+                  115,
                   116,
-                  111,
-                  107,
-                  101,
-                  110
+                  97,
+                  116,
+                  101
                 ]
-              },
-              {
-                "kind": "arg",
-                "path": "account"
-              },
-              {
-                "kind": "account",
-                "path": "mint"
               }
             ]
           }
         },
         {
-          "name": "user",
-          "writable": true,
+          "name": "owner",
           "signer": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "mint"
-        },
+          "name": "signer",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "bindWallet",
+      "discriminator": [
+        156,
+        226,
+        250,
+        174,
+        87,
+        6,
+        18,
+        131
+      ],
+      "accounts": [
         {
-          "name": "userToken",
-          "writable": true
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "programToken",
+          "name": "userTokenAccount",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  114,
-                  111,
-                  103,
+                  117,
+                  115,
+                  101,
                   114,
-                  97,
-                  109,
                   45,
                   116,
                   111,
@@ -199,6 +315,10 @@ export type Payment = {
                   110
                 ]
               },
+              {
+                "kind": "arg",
+                "path": "account"
+              },
               {
                 "kind": "account",
                 "path": "mint"
@@ -206,9 +326,18 @@ export type Payment = {
             ]
           }
         },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
         {
           "name": "record",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
@@ -229,18 +358,10 @@ export type Payment = {
             ]
           }
         },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
         },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
         {
           "name": "instructionSysvar"
         },
@@ -269,12 +390,8 @@ export type Payment = {
           }
         },
         {
-          "name": "amount",
-          "type": "u64"
-        },
-        {
-          "name": "frozen",
-          "type": "u64"
+          "name": "wallet",
+          "type": "pubkey"
         },
         {
           "name": "expiredAt",
@@ -292,16 +409,16 @@ export type Payment = {
       ]
     },
     {
-      "name": "freeze",
+      "name": "cancelEthSigner",
       "discriminator": [
-        255,
-        91,
-        207,
-        84,
-        251,
-        194,
-        254,
-        63
+        102,
+        14,
+        16,
+        98,
+        170,
+        166,
+        41,
+        136
       ],
       "accounts": [
         {
@@ -330,6 +447,51 @@ export type Payment = {
             ]
           }
         },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelExit",
+      "discriminator": [
+        32,
+        59,
+        254,
+        50,
+        60,
+        16,
+        49,
+        75
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
         {
           "name": "userTokenAccount",
           "writable": true,
@@ -372,6 +534,7 @@ export type Payment = {
         {
           "name": "record",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
@@ -423,10 +586,6 @@ export type Payment = {
             ]
           }
         },
-        {
-          "name": "amount",
-          "type": "u64"
-        },
         {
           "name": "expiredAt",
           "type": "i64"
@@ -443,16 +602,16 @@ export type Payment = {
       ]
     },
     {
-      "name": "initialize",
+      "name": "cancelFeeTo",
       "discriminator": [
-        175,
-        175,
-        109,
-        31,
-        13,
-        152,
-        155,
-        237
+        116,
+        157,
+        221,
+        131,
+        153,
+        184,
+        122,
+        33
       ],
       "accounts": [
         {
@@ -482,44 +641,27 @@ export type Payment = {
           }
         },
         {
-          "name": "owner",
-          "writable": true,
+          "name": "currentOwner",
           "signer": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
         }
       ],
       "args": []
     },
     {
-      "name": "initializeProgramToken",
+      "name": "cancelOwnership",
       "discriminator": [
-        9,
-        79,
-        172,
-        16,
-        22,
-        253,
-        20,
-        34
+        135,
+        159,
+        150,
+        121,
+        192,
+        185,
+        13,
+        182
       ],
       "accounts": [
         {
           "name": "paymentState",
-          "writable": true
-        },
-        {
-          "name": "owner",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "mint"
-        },
-        {
-          "name": "programToken",
           "writable": true,
           "pda": {
             "seeds": [
@@ -527,85 +669,87 @@ export type Payment = {
                 "kind": "const",
                 "value": [
                   112,
-                  114,
-                  111,
-                  103,
-                  114,
                   97,
+                  121,
                   109,
+                  101,
+                  110,
+                  116,
                   45,
+                  115,
                   116,
-                  111,
-                  107,
-                  101,
-                  110
+                  97,
+                  116,
+                  101
                 ]
-              },
-              {
-                "kind": "account",
-                "path": "mint"
               }
             ]
           }
         },
         {
-          "name": "feeTokenAccount",
+          "name": "currentOwner",
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelSigner",
+      "discriminator": [
+        225,
+        166,
+        255,
+        210,
+        218,
+        37,
+        33,
+        155
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
+                  112,
+                  97,
+                  121,
+                  109,
                   101,
-                  114,
+                  110,
+                  116,
                   45,
+                  115,
                   116,
-                  111,
-                  107,
-                  101,
-                  110
+                  97,
+                  116,
+                  101
                 ]
-              },
-              {
-                "kind": "account",
-                "path": "payment_state.fee_to_account",
-                "account": "paymentState"
-              },
-              {
-                "kind": "account",
-                "path": "mint"
               }
             ]
           }
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
+          "name": "currentOwner",
+          "signer": true
         }
       ],
       "args": []
     },
     {
-      "name": "settle",
+      "name": "changeEthSigner",
       "discriminator": [
+        45,
+        121,
+        202,
+        252,
+        7,
         175,
-        42,
-        185,
-        87,
-        144,
-        131,
-        102,
-        212
+        208,
+        74
       ],
       "accounts": [
         {
@@ -635,92 +779,200 @@ export type Payment = {
           }
         },
         {
-          "name": "fromTokenAccount",
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "ethSigner",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "changeFeeTo",
+      "discriminator": [
+        4,
+        215,
+        177,
+        253,
+        175,
+        90,
+        205,
+        197
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true
+        },
+        {
+          "name": "currentOwner",
+          "signer": true
+        },
+        {
+          "name": "newOwner"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "changeFeeToAccount",
+      "discriminator": [
+        48,
+        154,
+        80,
+        190,
+        66,
+        225,
+        33,
+        219
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
+                  112,
+                  97,
+                  121,
+                  109,
                   101,
-                  114,
+                  110,
+                  116,
                   45,
+                  115,
                   116,
-                  111,
-                  107,
-                  101,
-                  110
+                  97,
+                  116,
+                  101
                 ]
-              },
-              {
-                "kind": "arg",
-                "path": "deal.from"
-              },
-              {
-                "kind": "account",
-                "path": "mint"
               }
             ]
           }
         },
         {
-          "name": "toTokenAccount",
+          "name": "owner",
           "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  117,
-                  115,
-                  101,
-                  114,
-                  45,
-                  116,
-                  111,
-                  107,
-                  101,
-                  110
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "deal.to"
-              },
-              {
-                "kind": "account",
-                "path": "mint"
-              }
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "feeToAccount",
+          "type": {
+            "array": [
+              "u8",
+              32
             ]
           }
+        }
+      ]
+    },
+    {
+      "name": "changeOwner",
+      "discriminator": [
+        109,
+        40,
+        40,
+        90,
+        224,
+        120,
+        193,
+        184
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true
         },
         {
-          "name": "feeTokenAccount",
-          "writable": true,
+          "name": "currentOwner",
+          "signer": true
+        },
+        {
+          "name": "newOwner"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "changeSigner",
+      "discriminator": [
+        178,
+        235,
+        108,
+        157,
+        105,
+        50,
+        210,
+        90
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true
+        },
+        {
+          "name": "currentOwner",
+          "signer": true
+        },
+        {
+          "name": "newOwner"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "checkSolvency",
+      "discriminator": [
+        143,
+        80,
+        66,
+        232,
+        187,
+        126,
+        101,
+        195
+      ],
+      "accounts": [
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintTotals",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
-                  101,
-                  114,
+                  109,
+                  105,
+                  110,
+                  116,
                   45,
                   116,
                   111,
-                  107,
-                  101,
-                  110
+                  116,
+                  97,
+                  108,
+                  115
                 ]
               },
-              {
-                "kind": "account",
-                "path": "payment_state.fee_to_account",
-                "account": "paymentState"
-              },
               {
                 "kind": "account",
                 "path": "mint"
@@ -728,25 +980,8 @@ export type Payment = {
             ]
           }
         },
-        {
-          "name": "user",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "mint"
-        },
-        {
-          "name": "out",
-          "writable": true
-        },
-        {
-          "name": "feeUser",
-          "writable": true
-        },
         {
           "name": "programToken",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -775,97 +1010,30 @@ export type Payment = {
           }
         },
         {
-          "name": "record",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  114,
-                  101,
-                  99,
-                  111,
-                  114,
-                  100
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "sn"
-              }
-            ]
-          }
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "tokenProgram"
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "instructionSysvar"
-        },
-        {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "sn",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
-        },
-        {
-          "name": "deal",
-          "type": {
-            "defined": {
-              "name": "settlementData"
-            }
-          }
-        },
-        {
-          "name": "expiredAt",
-          "type": "i64"
-        },
-        {
-          "name": "signature",
-          "type": {
-            "array": [
-              "u8",
-              64
-            ]
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "transfer",
+      "name": "claimFees",
       "discriminator": [
-        163,
+        82,
+        251,
+        233,
+        156,
+        12,
         52,
-        200,
-        231,
-        140,
-        3,
-        69,
-        186
+        184,
+        202
       ],
       "accounts": [
         {
           "name": "paymentState",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -890,7 +1058,7 @@ export type Payment = {
           }
         },
         {
-          "name": "fromTokenAccount",
+          "name": "feeTokenAccount",
           "writable": true,
           "pda": {
             "seeds": [
@@ -910,8 +1078,9 @@ export type Payment = {
                 ]
               },
               {
-                "kind": "arg",
-                "path": "from"
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
               },
               {
                 "kind": "account",
@@ -921,29 +1090,34 @@ export type Payment = {
           }
         },
         {
-          "name": "toTokenAccount",
+          "name": "feeTo",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
-                  101,
-                  114,
-                  45,
+                  109,
+                  105,
+                  110,
                   116,
+                  45,
+                  99,
                   111,
-                  107,
-                  101,
-                  110
+                  110,
+                  102,
+                  105,
+                  103
                 ]
               },
-              {
-                "kind": "arg",
-                "path": "to"
-              },
               {
                 "kind": "account",
                 "path": "mint"
@@ -952,30 +1126,26 @@ export type Payment = {
           }
         },
         {
-          "name": "feeTokenAccount",
+          "name": "mintTotals",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
-                  101,
-                  114,
+                  109,
+                  105,
+                  110,
+                  116,
                   45,
                   116,
                   111,
-                  107,
-                  101,
-                  110
+                  116,
+                  97,
+                  108,
+                  115
                 ]
               },
-              {
-                "kind": "account",
-                "path": "payment_state.fee_to_account",
-                "account": "paymentState"
-              },
               {
                 "kind": "account",
                 "path": "mint"
@@ -984,19 +1154,7 @@ export type Payment = {
           }
         },
         {
-          "name": "user",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "mint"
-        },
-        {
-          "name": "out",
-          "writable": true
-        },
-        {
-          "name": "feeUser",
+          "name": "to",
           "writable": true
         },
         {
@@ -1030,110 +1188,80 @@ export type Payment = {
           }
         },
         {
-          "name": "record",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  114,
-                  101,
-                  99,
-                  111,
-                  114,
-                  100
-                ]
-              },
-              {
-                "kind": "arg",
-                "path": "sn"
-              }
-            ]
-          }
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "tokenProgram"
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "instructionSysvar"
-        },
-        {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
         }
       ],
       "args": [
-        {
-          "name": "sn",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
-        },
-        {
-          "name": "from",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
-        },
-        {
-          "name": "to",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
-        },
         {
           "name": "amount",
           "type": "u64"
-        },
+        }
+      ]
+    },
+    {
+      "name": "closeRecords",
+      "discriminator": [
+        37,
+        238,
+        211,
+        208,
+        66,
+        79,
+        182,
+        30
+      ],
+      "accounts": [
         {
-          "name": "fee",
-          "type": "u64"
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "expiredAt",
-          "type": "i64"
+          "name": "authority",
+          "signer": true
         },
         {
-          "name": "signature",
-          "type": {
-            "array": [
-              "u8",
-              64
-            ]
-          }
+          "name": "payer",
+          "writable": true
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "unfreeze",
+      "name": "deposit",
       "discriminator": [
-        133,
-        160,
-        68,
-        253,
-        80,
-        232,
-        218,
-        247
+        242,
+        35,
+        198,
+        137,
+        82,
+        225,
+        242,
+        182
       ],
       "accounts": [
         {
@@ -1184,7 +1312,7 @@ export type Payment = {
               },
               {
                 "kind": "arg",
-                "path": "from"
+                "path": "account"
               },
               {
                 "kind": "account",
@@ -1194,30 +1322,34 @@ export type Payment = {
           }
         },
         {
-          "name": "feeTokenAccount",
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  117,
-                  115,
-                  101,
-                  114,
-                  45,
+                  109,
+                  105,
+                  110,
                   116,
+                  45,
+                  99,
                   111,
-                  107,
-                  101,
-                  110
+                  110,
+                  102,
+                  105,
+                  103
                 ]
               },
-              {
-                "kind": "account",
-                "path": "payment_state.fee_to_account",
-                "account": "paymentState"
-              },
               {
                 "kind": "account",
                 "path": "mint"
@@ -1226,52 +1358,145 @@ export type Payment = {
           }
         },
         {
-          "name": "user",
+          "name": "mintTotals",
           "writable": true,
-          "signer": true
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
         },
         {
-          "name": "mint"
+          "name": "userToken",
+          "writable": true
         },
         {
-          "name": "record",
+          "name": "programToken",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  112,
                   114,
-                  101,
-                  99,
                   111,
+                  103,
                   114,
-                  100
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
                 ]
               },
               {
-                "kind": "arg",
-                "path": "sn"
+                "kind": "account",
+                "path": "mint"
               }
             ]
           }
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "instructionSysvar"
-        },
-        {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "sn",
-          "type": {
+          "name": "nativeUnwrap",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  97,
+                  116,
+                  105,
+                  118,
+                  101,
+                  45,
+                  117,
+                  110,
+                  119,
+                  114,
+                  97,
+                  112
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "instructionSysvar"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
             "array": [
               "u8",
               32
@@ -1292,7 +1517,7 @@ export type Payment = {
           "type": "u64"
         },
         {
-          "name": "fee",
+          "name": "frozen",
           "type": "u64"
         },
         {
@@ -1311,21 +1536,20 @@ export type Payment = {
       ]
     },
     {
-      "name": "withdraw",
+      "name": "executeExit",
       "discriminator": [
-        183,
-        18,
-        70,
-        156,
-        148,
-        109,
-        161,
-        34
+        130,
+        90,
+        198,
+        58,
+        73,
+        242,
+        0,
+        236
       ],
       "accounts": [
         {
           "name": "paymentState",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -1371,7 +1595,7 @@ export type Payment = {
               },
               {
                 "kind": "arg",
-                "path": "from"
+                "path": "account"
               },
               {
                 "kind": "account",
@@ -1381,38 +1605,59 @@ export type Payment = {
           }
         },
         {
-          "name": "user",
-          "writable": true,
+          "name": "wallet",
           "signer": true
         },
         {
           "name": "mint"
         },
         {
-          "name": "to",
-          "writable": true
+          "name": "mintConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
         },
         {
-          "name": "programToken",
+          "name": "mintTotals",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  114,
-                  111,
-                  103,
-                  114,
-                  97,
                   109,
+                  105,
+                  110,
+                  116,
                   45,
                   116,
                   111,
-                  107,
-                  101,
-                  110
+                  116,
+                  97,
+                  108,
+                  115
                 ]
               },
               {
@@ -1423,369 +1668,5251 @@ export type Payment = {
           }
         },
         {
-          "name": "record",
+          "name": "to",
+          "writable": true
+        },
+        {
+          "name": "programToken",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  112,
                   114,
-                  101,
-                  99,
                   111,
+                  103,
                   114,
-                  100
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
                 ]
               },
               {
-                "kind": "arg",
-                "path": "sn"
+                "kind": "account",
+                "path": "mint"
               }
             ]
           }
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "tokenProgram"
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "instructionSysvar"
-        },
-        {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "sn",
+          "name": "account",
           "type": {
             "array": [
               "u8",
               32
             ]
           }
-        },
+        }
+      ]
+    },
+    {
+      "name": "freeze",
+      "discriminator": [
+        255,
+        91,
+        207,
+        84,
+        251,
+        194,
+        254,
+        63
+      ],
+      "accounts": [
         {
-          "name": "from",
-          "type": {
-            "array": [
-              "u8",
-              32
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
             ]
           }
         },
         {
-          "name": "available",
-          "type": "u64"
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "account"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
         },
         {
-          "name": "frozen",
-          "type": "u64"
+          "name": "user",
+          "writable": true,
+          "signer": true
         },
         {
-          "name": "expiredAt",
-          "type": "i64"
+          "name": "mint"
         },
         {
-          "name": "signature",
-          "type": {
-            "array": [
-              "u8",
-              64
-            ]
-          }
-        }
-      ]
-    }
-  ],
-  "accounts": [
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "hold",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  108,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "holdId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "instructionSysvar"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "account",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "holdId",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "beneficiary",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "releaseAt",
+          "type": "i64"
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "initialize",
+      "discriminator": [
+        175,
+        175,
+        109,
+        31,
+        13,
+        152,
+        155,
+        237
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeProgramToken",
+      "discriminator": [
+        9,
+        79,
+        172,
+        16,
+        22,
+        253,
+        20,
+        34
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "programToken",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateFeeAccount",
+      "discriminator": [
+        223,
+        60,
+        126,
+        177,
+        109,
+        146,
+        65,
+        81
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "oldFeeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.previous_fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migratePaymentState",
+      "discriminator": [
+        228,
+        233,
+        226,
+        163,
+        87,
+        205,
+        93,
+        251
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateUserTokenAccount",
+      "discriminator": [
+        106,
+        162,
+        80,
+        49,
+        184,
+        200,
+        216,
+        131
+      ],
+      "accounts": [
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "account"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "account",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "releaseHold",
+      "discriminator": [
+        106,
+        109,
+        70,
+        162,
+        197,
+        158,
+        92,
+        243
+      ],
+      "accounts": [
+        {
+          "name": "hold",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  108,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "holdId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "hold.account",
+                "account": "hold"
+              },
+              {
+                "kind": "account",
+                "path": "hold.mint",
+                "account": "hold"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "hold.mint",
+                "account": "hold"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true
+        },
+        {
+          "name": "user",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "holdId",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "removeQuorumSigner",
+      "discriminator": [
+        95,
+        3,
+        162,
+        97,
+        149,
+        135,
+        199,
+        171
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "signer",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "requestExit",
+      "discriminator": [
+        121,
+        186,
+        203,
+        74,
+        138,
+        218,
+        135,
+        151
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "account"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "wallet",
+          "signer": true
+        },
+        {
+          "name": "mint"
+        }
+      ],
+      "args": [
+        {
+          "name": "account",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "seedMintTotals",
+      "discriminator": [
+        41,
+        244,
+        181,
+        129,
+        164,
+        37,
+        154,
+        155
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "totalAvailable",
+          "type": "u64"
+        },
+        {
+          "name": "totalFrozen",
+          "type": "u64"
+        },
+        {
+          "name": "totalFees",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setEnabled",
+      "discriminator": [
+        108,
+        151,
+        239,
+        151,
+        181,
+        233,
+        110,
+        123
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setExitDelay",
+      "discriminator": [
+        245,
+        192,
+        93,
+        59,
+        250,
+        25,
+        115,
+        149
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "delay",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setMintConfig",
+      "discriminator": [
+        233,
+        25,
+        222,
+        17,
+        45,
+        155,
+        113,
+        137
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "mintConfigParams"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "setNosnEnabled",
+      "discriminator": [
+        177,
+        177,
+        249,
+        119,
+        24,
+        47,
+        18,
+        68
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setPaused",
+      "discriminator": [
+        91,
+        60,
+        125,
+        192,
+        176,
+        225,
+        166,
+        218
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setQuorumThreshold",
+      "discriminator": [
+        23,
+        59,
+        11,
+        141,
+        244,
+        76,
+        79,
+        146
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setRecordRetention",
+      "discriminator": [
+        76,
+        50,
+        181,
+        128,
+        177,
+        113,
+        0,
+        92
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "retention",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "setSignatureScheme",
+      "discriminator": [
+        171,
+        115,
+        26,
+        184,
+        13,
+        160,
+        145,
+        27
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "scheme",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setSignerOverlap",
+      "discriminator": [
+        45,
+        226,
+        97,
+        90,
+        243,
+        53,
+        244,
+        134
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "overlap",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "settle",
+      "discriminator": [
+        175,
+        42,
+        185,
+        87,
+        144,
+        131,
+        102,
+        212
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "fromTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deal.from"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "toTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deal.to"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "out",
+          "writable": true
+        },
+        {
+          "name": "feeUser",
+          "writable": true
+        },
+        {
+          "name": "programToken",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "hold",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  108,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deal.hold_id"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "instructionSysvar"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "deal",
+          "type": {
+            "defined": {
+              "name": "settlementData"
+            }
+          }
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "settleBatch",
+      "discriminator": [
+        22,
+        2,
+        21,
+        223,
+        225,
+        122,
+        163,
+        214
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "instructionSysvar"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "deals",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "settlementData"
+              }
+            }
+          }
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "settleSplit",
+      "discriminator": [
+        3,
+        170,
+        152,
+        217,
+        94,
+        9,
+        9,
+        213
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "fromTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deal.from"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "programToken",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "hold",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  108,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "deal.hold_id"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "instructionSysvar"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "deal",
+          "type": {
+            "defined": {
+              "name": "splitSettlementData"
+            }
+          }
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "sweepSurplus",
+      "discriminator": [
+        144,
+        67,
+        197,
+        177,
+        218,
+        200,
+        50,
+        24
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintTotals",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "to",
+          "writable": true
+        },
+        {
+          "name": "programToken",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "transfer",
+      "discriminator": [
+        163,
+        52,
+        200,
+        231,
+        140,
+        3,
+        69,
+        186
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "fromTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "from"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "toTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "to"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "out",
+          "writable": true
+        },
+        {
+          "name": "feeUser",
+          "writable": true
+        },
+        {
+          "name": "programToken",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "instructionSysvar"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "from",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "to",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "fee",
+          "type": "u64"
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "transferMany",
+      "discriminator": [
+        115,
+        138,
+        249,
+        83,
+        190,
+        57,
+        126,
+        164
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "fromTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "from"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "instructionSysvar"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "from",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "recipients",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "transferRecipient"
+              }
+            }
+          }
+        },
+        {
+          "name": "fee",
+          "type": "u64"
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "unfreeze",
+      "discriminator": [
+        133,
+        160,
+        68,
+        253,
+        80,
+        232,
+        218,
+        247
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "from"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "payment_state.fee_to_account",
+                "account": "paymentState"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "hold",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  111,
+                  108,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "holdId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "instructionSysvar"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "account",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "fee",
+          "type": "u64"
+        },
+        {
+          "name": "holdId",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "withdraw",
+      "discriminator": [
+        183,
+        18,
+        70,
+        156,
+        148,
+        109,
+        161,
+        34
+      ],
+      "accounts": [
+        {
+          "name": "paymentState",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  121,
+                  109,
+                  101,
+                  110,
+                  116,
+                  45,
+                  115,
+                  116,
+                  97,
+                  116,
+                  101
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "userTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "from"
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "mint"
+        },
+        {
+          "name": "mintConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintTotals",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  45,
+                  116,
+                  111,
+                  116,
+                  97,
+                  108,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "to",
+          "writable": true
+        },
+        {
+          "name": "programToken",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  103,
+                  114,
+                  97,
+                  109,
+                  45,
+                  116,
+                  111,
+                  107,
+                  101,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "mint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "record",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  99,
+                  111,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "sn"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "instructionSysvar"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "sn",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "from",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "available",
+          "type": "u64"
+        },
+        {
+          "name": "frozen",
+          "type": "u64"
+        },
+        {
+          "name": "expiredAt",
+          "type": "i64"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "hold",
+      "discriminator": [
+        110,
+        65,
+        238,
+        142,
+        146,
+        91,
+        196,
+        171
+      ]
+    },
+    {
+      "name": "mintConfig",
+      "discriminator": [
+        168,
+        252,
+        88,
+        182,
+        219,
+        205,
+        39,
+        53
+      ]
+    },
+    {
+      "name": "mintTotals",
+      "discriminator": [
+        254,
+        176,
+        169,
+        206,
+        145,
+        93,
+        248,
+        163
+      ]
+    },
+    {
+      "name": "paymentState",
+      "discriminator": [
+        108,
+        22,
+        191,
+        124,
+        9,
+        73,
+        22,
+        238
+      ]
+    },
+    {
+      "name": "transactionRecord",
+      "discriminator": [
+        206,
+        23,
+        5,
+        97,
+        161,
+        157,
+        25,
+        107
+      ]
+    },
+    {
+      "name": "userTokenAccount",
+      "discriminator": [
+        54,
+        43,
+        28,
+        109,
+        148,
+        154,
+        11,
+        34
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "batchSettleEvent",
+      "discriminator": [
+        208,
+        121,
+        66,
+        233,
+        183,
+        252,
+        112,
+        113
+      ]
+    },
+    {
+      "name": "depositEvent",
+      "discriminator": [
+        120,
+        248,
+        61,
+        83,
+        31,
+        142,
+        107,
+        144
+      ]
+    },
+    {
+      "name": "exitCancelledEvent",
+      "discriminator": [
+        92,
+        127,
+        106,
+        201,
+        9,
+        27,
+        154,
+        133
+      ]
+    },
+    {
+      "name": "exitExecutedEvent",
+      "discriminator": [
+        76,
+        199,
+        222,
+        48,
+        133,
+        63,
+        127,
+        109
+      ]
+    },
+    {
+      "name": "exitRequestedEvent",
+      "discriminator": [
+        133,
+        221,
+        38,
+        105,
+        177,
+        51,
+        98,
+        61
+      ]
+    },
+    {
+      "name": "feeAccountMigratedEvent",
+      "discriminator": [
+        142,
+        199,
+        205,
+        103,
+        169,
+        156,
+        186,
+        50
+      ]
+    },
+    {
+      "name": "feeClaimedEvent",
+      "discriminator": [
+        42,
+        25,
+        34,
+        217,
+        30,
+        24,
+        89,
+        139
+      ]
+    },
+    {
+      "name": "feeToAccountChangedEvent",
+      "discriminator": [
+        12,
+        116,
+        29,
+        223,
+        44,
+        71,
+        114,
+        34
+      ]
+    },
+    {
+      "name": "freezeEvent",
+      "discriminator": [
+        71,
+        23,
+        242,
+        12,
+        63,
+        34,
+        225,
+        159
+      ]
+    },
+    {
+      "name": "holdReleasedEvent",
+      "discriminator": [
+        126,
+        201,
+        183,
+        246,
+        128,
+        234,
+        80,
+        170
+      ]
+    },
+    {
+      "name": "mintTotalsSeededEvent",
+      "discriminator": [
+        170,
+        165,
+        239,
+        83,
+        236,
+        138,
+        51,
+        102
+      ]
+    },
+    {
+      "name": "pauseChangedEvent",
+      "discriminator": [
+        128,
+        117,
+        138,
+        46,
+        101,
+        209,
+        137,
+        171
+      ]
+    },
+    {
+      "name": "settleEvent",
+      "discriminator": [
+        14,
+        166,
+        206,
+        248,
+        35,
+        1,
+        134,
+        48
+      ]
+    },
+    {
+      "name": "settleSplitEvent",
+      "discriminator": [
+        162,
+        93,
+        164,
+        77,
+        81,
+        0,
+        220,
+        167
+      ]
+    },
+    {
+      "name": "solvencyEvent",
+      "discriminator": [
+        70,
+        33,
+        36,
+        5,
+        236,
+        74,
+        99,
+        160
+      ]
+    },
+    {
+      "name": "surplusSweptEvent",
+      "discriminator": [
+        42,
+        179,
+        138,
+        242,
+        247,
+        235,
+        183,
+        192
+      ]
+    },
+    {
+      "name": "transferEvent",
+      "discriminator": [
+        100,
+        10,
+        46,
+        113,
+        8,
+        28,
+        179,
+        125
+      ]
+    },
+    {
+      "name": "transferManyEvent",
+      "discriminator": [
+        59,
+        10,
+        179,
+        223,
+        120,
+        22,
+        48,
+        18
+      ]
+    },
+    {
+      "name": "unfreezeEvent",
+      "discriminator": [
+        190,
+        183,
+        94,
+        43,
+        184,
+        66,
+        229,
+        113
+      ]
+    },
+    {
+      "name": "walletBoundEvent",
+      "discriminator": [
+        10,
+        195,
+        32,
+        12,
+        192,
+        64,
+        151,
+        124
+      ]
+    },
+    {
+      "name": "withdrawEvent",
+      "discriminator": [
+        22,
+        9,
+        133,
+        26,
+        160,
+        44,
+        71,
+        192
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "disabled",
+      "msg": "The program is disabled"
+    },
+    {
+      "code": 6001,
+      "name": "invalidParameter",
+      "msg": "Invalid parameter"
+    },
+    {
+      "code": 6002,
+      "name": "invalidAmount",
+      "msg": "Invalid amount"
+    },
+    {
+      "code": 6003,
+      "name": "feeOverrun",
+      "msg": "Fee overrun"
+    },
+    {
+      "code": 6004,
+      "name": "expired",
+      "msg": "Transaction expired"
+    },
+    {
+      "code": 6005,
+      "name": "invalidSignature",
+      "msg": "Invalid signature"
+    },
+    {
+      "code": 6006,
+      "name": "alreadyExecuted",
+      "msg": "Transaction already executed"
+    },
+    {
+      "code": 6007,
+      "name": "invalidMint",
+      "msg": "Invalid mint"
+    },
+    {
+      "code": 6008,
+      "name": "forbidden",
+      "msg": "forbidden"
+    },
+    {
+      "code": 6009,
+      "name": "zeroAmount",
+      "msg": "Cannot all be zero"
+    },
+    {
+      "code": 6010,
+      "name": "insufficientFunds",
+      "msg": "Insufficient funds"
+    },
+    {
+      "code": 6011,
+      "name": "insufficientAvailable",
+      "msg": "Insufficient available balance"
+    },
+    {
+      "code": 6012,
+      "name": "insufficientFrozen",
+      "msg": "Insufficient frozen balance"
+    },
+    {
+      "code": 6013,
+      "name": "invalidProgramToken",
+      "msg": "Invalid program token"
+    },
+    {
+      "code": 6014,
+      "name": "unauthorized",
+      "msg": "unauthorized"
+    },
+    {
+      "code": 6015,
+      "name": "missingEd25519Instruction",
+      "msg": "Missing Ed25519 instruction"
+    },
+    {
+      "code": 6016,
+      "name": "invalidEd25519Instruction",
+      "msg": "Invalid Ed25519 instruction"
+    },
+    {
+      "code": 6017,
+      "name": "invalidPublicKey",
+      "msg": "Invalid public key"
+    },
+    {
+      "code": 6018,
+      "name": "invalidMessage",
+      "msg": "Invalid message"
+    },
+    {
+      "code": 6019,
+      "name": "invalidFeeUser",
+      "msg": "Invalid fee user"
+    },
+    {
+      "code": 6020,
+      "name": "invalidAtaOwner",
+      "msg": "Invalid ATA owner"
+    },
+    {
+      "code": 6021,
+      "name": "paused",
+      "msg": "The operation is paused"
+    },
+    {
+      "code": 6022,
+      "name": "missingRecord",
+      "msg": "Missing transaction record"
+    },
+    {
+      "code": 6023,
+      "name": "invalidNonce",
+      "msg": "Invalid nonce"
+    },
+    {
+      "code": 6024,
+      "name": "recordNotExpired",
+      "msg": "Record is still within its retention window"
+    },
+    {
+      "code": 6025,
+      "name": "invalidSignatureCount",
+      "msg": "Invalid Ed25519 signature count"
+    },
+    {
+      "code": 6026,
+      "name": "invalidInstructionIndex",
+      "msg": "Invalid Ed25519 instruction index"
+    },
+    {
+      "code": 6027,
+      "name": "invalidDataOffset",
+      "msg": "Invalid Ed25519 data offset"
+    },
+    {
+      "code": 6028,
+      "name": "tooManySigners",
+      "msg": "Too many quorum signers"
+    },
+    {
+      "code": 6029,
+      "name": "signerAlreadyExists",
+      "msg": "Quorum signer already exists"
+    },
     {
-      "name": "paymentState",
-      "discriminator": [
-        108,
-        22,
-        191,
-        124,
-        9,
-        73,
-        22,
-        238
-      ]
+      "code": 6030,
+      "name": "signerNotFound",
+      "msg": "Quorum signer not found"
     },
     {
-      "name": "transactionRecord",
-      "discriminator": [
-        206,
-        23,
-        5,
-        97,
-        161,
-        157,
-        25,
-        107
-      ]
+      "code": 6031,
+      "name": "invalidThreshold",
+      "msg": "Invalid quorum threshold"
     },
     {
-      "name": "userTokenAccount",
-      "discriminator": [
-        54,
-        43,
-        28,
-        109,
-        148,
-        154,
-        11,
-        34
-      ]
-    }
-  ],
-  "events": [
+      "code": 6032,
+      "name": "insufficientApprovals",
+      "msg": "Insufficient quorum approvals"
+    },
     {
-      "name": "depositEvent",
-      "discriminator": [
-        120,
-        248,
-        61,
-        83,
-        31,
-        142,
-        107,
-        144
-      ]
+      "code": 6033,
+      "name": "invalidSignatureScheme",
+      "msg": "Invalid signature scheme"
     },
     {
-      "name": "freezeEvent",
-      "discriminator": [
-        71,
-        23,
-        242,
-        12,
-        63,
-        34,
-        225,
-        159
-      ]
+      "code": 6034,
+      "name": "quorumRequiresEd25519",
+      "msg": "The quorum requires the Ed25519 signature scheme"
     },
     {
-      "name": "settleEvent",
-      "discriminator": [
-        14,
-        166,
-        206,
-        248,
-        35,
-        1,
-        134,
-        48
-      ]
+      "code": 6035,
+      "name": "missingSecp256k1Instruction",
+      "msg": "Missing Secp256k1 instruction"
     },
     {
-      "name": "transferEvent",
-      "discriminator": [
-        100,
-        10,
-        46,
-        113,
-        8,
-        28,
-        179,
-        125
-      ]
+      "code": 6036,
+      "name": "invalidSecp256k1Instruction",
+      "msg": "Invalid Secp256k1 instruction"
     },
     {
-      "name": "unfreezeEvent",
-      "discriminator": [
-        190,
-        183,
-        94,
-        43,
-        184,
-        66,
-        229,
-        113
-      ]
+      "code": 6037,
+      "name": "mintNotEnabled",
+      "msg": "Mint is not enabled"
     },
     {
-      "name": "withdrawEvent",
-      "discriminator": [
-        22,
-        9,
-        133,
-        26,
-        160,
-        44,
-        71,
-        192
-      ]
-    }
-  ],
-  "errors": [
+      "code": 6038,
+      "name": "depositTooSmall",
+      "msg": "Deposit is below the minimum"
+    },
     {
-      "code": 6000,
-      "name": "disabled",
-      "msg": "The program is disabled"
+      "code": 6039,
+      "name": "depositTooLarge",
+      "msg": "Deposit is above the maximum"
     },
     {
-      "code": 6001,
-      "name": "invalidParameter",
-      "msg": "Invalid parameter"
+      "code": 6040,
+      "name": "balanceCapExceeded",
+      "msg": "Account balance cap exceeded"
     },
     {
-      "code": 6002,
-      "name": "invalidAmount",
-      "msg": "Invalid amount"
+      "code": 6041,
+      "name": "depositCapExceeded",
+      "msg": "Global deposit cap exceeded"
+    },
+    {
+      "code": 6042,
+      "name": "insolvent",
+      "msg": "Liabilities exceed the vault balance"
+    },
+    {
+      "code": 6043,
+      "name": "insufficientSurplus",
+      "msg": "Amount exceeds the vault surplus"
+    },
+    {
+      "code": 6044,
+      "name": "vaultRentReserve",
+      "msg": "SOL payout would leave the vault below its rent-exempt reserve"
+    },
+    {
+      "code": 6045,
+      "name": "exitDisabled",
+      "msg": "Exits are disabled"
+    },
+    {
+      "code": 6046,
+      "name": "walletNotBound",
+      "msg": "No wallet is bound to the account"
+    },
+    {
+      "code": 6047,
+      "name": "exitNotRequested",
+      "msg": "No exit has been requested"
+    },
+    {
+      "code": 6048,
+      "name": "exitNotReady",
+      "msg": "Exit delay has not elapsed"
+    },
+    {
+      "code": 6049,
+      "name": "missingHold",
+      "msg": "Missing hold account"
+    },
+    {
+      "code": 6050,
+      "name": "missingNativeUnwrap",
+      "msg": "Missing native unwrap account"
+    },
+    {
+      "code": 6051,
+      "name": "holdNotReleasable",
+      "msg": "Hold has not reached its release time"
+    },
+    {
+      "code": 6052,
+      "name": "invalidUserTokenAccount",
+      "msg": "Invalid user token account"
+    },
+    {
+      "code": 6053,
+      "name": "invalidPayees",
+      "msg": "Invalid payee list"
+    },
+    {
+      "code": 6054,
+      "name": "invalidBatch",
+      "msg": "Invalid batch"
+    },
+    {
+      "code": 6055,
+      "name": "invalidRecipients",
+      "msg": "Invalid recipients"
+    },
+    {
+      "code": 6056,
+      "name": "totalsNotSeeded",
+      "msg": "Mint totals have not been seeded"
+    },
+    {
+      "code": 6057,
+      "name": "totalsAlreadySeeded",
+      "msg": "Mint totals have already been seeded"
+    }
+  ],
+  "types": [
+    {
+      "name": "batchSettleEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sn",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "index",
+            "type": "u16"
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "from",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "to",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "available",
+            "type": "u64"
+          },
+          {
+            "name": "frozen",
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "paid",
+            "type": "u64"
+          },
+          {
+            "name": "excessFee",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "depositEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sn",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "account",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "received",
+            "type": "u64"
+          },
+          {
+            "name": "frozen",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6003,
-      "name": "feeOverrun",
-      "msg": "Fee overrun"
+      "name": "exitCancelledEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sn",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "account",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "expired",
-      "msg": "Transaction expired"
+      "name": "exitExecutedEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "account",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "to",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "received",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "invalidSignature",
-      "msg": "Invalid signature"
+      "name": "exitRequestedEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "account",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "executableAt",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "alreadyExecuted",
-      "msg": "Transaction already executed"
+      "name": "feeAccountMigratedEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "from",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "to",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "available",
+            "type": "u64"
+          },
+          {
+            "name": "frozen",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "invalidMint",
-      "msg": "Invalid mint"
+      "name": "feeClaimedEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "from",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "to",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "forbidden",
-      "msg": "forbidden"
+      "name": "feeToAccountChangedEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "oldFeeToAccount",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "newFeeToAccount",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "zeroAmount",
-      "msg": "Cannot all be zero"
+      "name": "freezeEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sn",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "account",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "holdId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "beneficiary",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "releaseAt",
+            "type": "i64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "insufficientFunds",
-      "msg": "Insufficient funds"
+      "name": "hold",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "account",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "beneficiary",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "releaseAt",
+            "type": "i64"
+          },
+          {
+            "name": "payer",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "insufficientAvailable",
-      "msg": "Insufficient available balance"
+      "name": "holdReleasedEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "holdId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "account",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "insufficientFrozen",
-      "msg": "Insufficient frozen balance"
+      "name": "mintConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "decimals",
+            "type": "u8"
+          },
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "minDeposit",
+            "type": "u64"
+          },
+          {
+            "name": "maxDeposit",
+            "type": "u64"
+          },
+          {
+            "name": "maxBalance",
+            "type": "u64"
+          },
+          {
+            "name": "depositCap",
+            "type": "u64"
+          },
+          {
+            "name": "totalDeposits",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6013,
-      "name": "invalidProgramToken",
-      "msg": "Invalid program token"
+      "name": "mintConfigParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "minDeposit",
+            "type": "u64"
+          },
+          {
+            "name": "maxDeposit",
+            "type": "u64"
+          },
+          {
+            "name": "maxBalance",
+            "type": "u64"
+          },
+          {
+            "name": "depositCap",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6014,
-      "name": "unauthorized",
-      "msg": "unauthorized"
+      "name": "mintTotals",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalAvailable",
+            "type": "u64"
+          },
+          {
+            "name": "totalFrozen",
+            "type": "u64"
+          },
+          {
+            "name": "totalFees",
+            "type": "u64"
+          },
+          {
+            "name": "rentReserve",
+            "type": "u64"
+          },
+          {
+            "name": "seeded",
+            "type": "bool"
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "missingEd25519Instruction",
-      "msg": "Missing Ed25519 instruction"
+      "name": "mintTotalsSeededEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "totalAvailable",
+            "type": "u64"
+          },
+          {
+            "name": "totalFrozen",
+            "type": "u64"
+          },
+          {
+            "name": "totalFees",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6016,
-      "name": "invalidEd25519Instruction",
-      "msg": "Invalid Ed25519 instruction"
+      "name": "pauseChangedEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "paused",
+            "type": "u8"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6017,
-      "name": "invalidPublicKey",
-      "msg": "Invalid public key"
+      "name": "paymentState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "nosnEnabled",
+            "type": "bool"
+          },
+          {
+            "name": "signer",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeTo",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeToAccount",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "paused",
+            "type": "u8"
+          },
+          {
+            "name": "pendingOwner",
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingSigner",
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingFeeTo",
+            "type": "pubkey"
+          },
+          {
+            "name": "recordRetention",
+            "type": "i64"
+          },
+          {
+            "name": "quorumSigners",
+            "type": {
+              "vec": "pubkey"
+            }
+          },
+          {
+            "name": "quorumThreshold",
+            "type": "u8"
+          },
+          {
+            "name": "previousSigner",
+            "type": "pubkey"
+          },
+          {
+            "name": "previousValidUntil",
+            "type": "i64"
+          },
+          {
+            "name": "signerOverlap",
+            "type": "i64"
+          },
+          {
+            "name": "signatureScheme",
+            "type": "u8"
+          },
+          {
+            "name": "ethSigner",
+            "type": {
+              "array": [
+                "u8",
+                20
+              ]
+            }
+          },
+          {
+            "name": "exitDelay",
+            "type": "i64"
+          },
+          {
+            "name": "previousFeeToAccount",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "pendingEthSigner",
+            "type": {
+              "array": [
+                "u8",
+                20
+              ]
+            }
+          },
+          {
+            "name": "previousEthSigner",
+            "type": {
+              "array": [
+                "u8",
+                20
+              ]
+            }
+          },
+          {
+            "name": "previousEthValidUntil",
+            "type": "i64"
+          }
+        ]
+      }
     },
     {
-      "code": 6018,
-      "name": "invalidMessage",
-      "msg": "Invalid message"
+      "name": "settleEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sn",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "from",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "to",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "out",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeUser",
+            "type": "pubkey"
+          },
+          {
+            "name": "available",
+            "type": "u64"
+          },
+          {
+            "name": "frozen",
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "paid",
+            "type": "u64"
+          },
+          {
+            "name": "excessFee",
+            "type": "u64"
+          },
+          {
+            "name": "holdId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "received",
+            "type": "u64"
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6019,
-      "name": "invalidFeeUser",
-      "msg": "Invalid fee user"
+      "name": "settleSplitEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "sn",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "from",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "available",
+            "type": "u64"
+          },
+          {
+            "name": "frozen",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "holdId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "payees",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "splitPayee"
+                }
+              }
+            }
+          },
+          {
+            "name": "received",
+            "type": {
+              "vec": "u64"
+            }
+          },
+          {
+            "name": "user",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6020,
-      "name": "invalidAtaOwner",
-      "msg": "Invalid ATA owner"
-    }
-  ],
-  "types": [
-    {
-      "name": "depositEvent",
+      "name": "settlementData",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "sn",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "from",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "to",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "available",
+            "type": "u64"
+          },
+          {
+            "name": "frozen",
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "paid",
+            "type": "u64"
           },
           {
-            "name": "account",
+            "name": "excessFee",
+            "type": "u64"
+          },
+          {
+            "name": "holdId",
             "type": {
               "array": [
                 "u8",
                 32
               ]
             }
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "solvencyEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
             "name": "token",
             "type": "pubkey"
           },
           {
-            "name": "amount",
+            "name": "assets",
             "type": "u64"
           },
           {
-            "name": "frozen",
+            "name": "liabilities",
             "type": "u64"
           },
           {
-            "name": "user",
-            "type": "pubkey"
+            "name": "totalAvailable",
+            "type": "u64"
+          },
+          {
+            "name": "totalFrozen",
+            "type": "u64"
+          },
+          {
+            "name": "totalFees",
+            "type": "u64"
+          },
+          {
+            "name": "rentReserve",
+            "type": "u64"
+          },
+          {
+            "name": "solvent",
+            "type": "bool"
           }
         ]
       }
     },
     {
-      "name": "freezeEvent",
+      "name": "splitPayee",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "sn",
+            "name": "account",
             "type": {
               "array": [
                 "u8",
@@ -1794,7 +6921,23 @@ export type Payment = {
             }
           },
           {
-            "name": "account",
+            "name": "out",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "splitSettlementData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "from",
             "type": {
               "array": [
                 "u8",
@@ -1803,63 +6946,89 @@ export type Payment = {
             }
           },
           {
-            "name": "token",
-            "type": "pubkey"
+            "name": "available",
+            "type": "u64"
           },
           {
-            "name": "amount",
+            "name": "frozen",
             "type": "u64"
           },
           {
-            "name": "user",
-            "type": "pubkey"
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "holdId",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "payees",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "splitPayee"
+                }
+              }
+            }
           }
         ]
       }
     },
     {
-      "name": "paymentState",
+      "name": "surplusSweptEvent",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "owner",
+            "name": "token",
             "type": "pubkey"
           },
           {
-            "name": "enabled",
-            "type": "bool"
+            "name": "to",
+            "type": "pubkey"
           },
           {
-            "name": "nosnEnabled",
-            "type": "bool"
+            "name": "amount",
+            "type": "u64"
           },
           {
-            "name": "signer",
-            "type": "pubkey"
+            "name": "surplus",
+            "type": "u64"
           },
           {
-            "name": "feeTo",
+            "name": "user",
             "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "transactionRecord",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "executed",
+            "type": "bool"
           },
           {
-            "name": "feeToAccount",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "expiredAt",
+            "type": "i64"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "payer",
+            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "settleEvent",
+      "name": "transferEvent",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1902,14 +7071,6 @@ export type Payment = {
             "name": "feeUser",
             "type": "pubkey"
           },
-          {
-            "name": "available",
-            "type": "u64"
-          },
-          {
-            "name": "frozen",
-            "type": "u64"
-          },
           {
             "name": "amount",
             "type": "u64"
@@ -1919,11 +7080,7 @@ export type Payment = {
             "type": "u64"
           },
           {
-            "name": "paid",
-            "type": "u64"
-          },
-          {
-            "name": "excessFee",
+            "name": "received",
             "type": "u64"
           },
           {
@@ -1934,12 +7091,12 @@ export type Payment = {
       }
     },
     {
-      "name": "settlementData",
+      "name": "transferManyEvent",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "from",
+            "name": "sn",
             "type": {
               "array": [
                 "u8",
@@ -1948,7 +7105,11 @@ export type Payment = {
             }
           },
           {
-            "name": "to",
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "from",
             "type": {
               "array": [
                 "u8",
@@ -1957,15 +7118,17 @@ export type Payment = {
             }
           },
           {
-            "name": "available",
-            "type": "u64"
-          },
-          {
-            "name": "frozen",
-            "type": "u64"
+            "name": "recipients",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "transferRecipient"
+                }
+              }
+            }
           },
           {
-            "name": "amount",
+            "name": "total",
             "type": "u64"
           },
           {
@@ -1973,30 +7136,35 @@ export type Payment = {
             "type": "u64"
           },
           {
-            "name": "paid",
-            "type": "u64"
-          },
-          {
-            "name": "excessFee",
-            "type": "u64"
+            "name": "user",
+            "type": "pubkey"
           }
         ]
       }
     },
     {
-      "name": "transactionRecord",
+      "name": "transferRecipient",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "executed",
-            "type": "bool"
+            "name": "to",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "transferEvent",
+      "name": "unfreezeEvent",
       "type": {
         "kind": "struct",
         "fields": [
@@ -2010,11 +7178,7 @@ export type Payment = {
             }
           },
           {
-            "name": "token",
-            "type": "pubkey"
-          },
-          {
-            "name": "from",
+            "name": "account",
             "type": {
               "array": [
                 "u8",
@@ -2023,7 +7187,19 @@ export type Payment = {
             }
           },
           {
-            "name": "to",
+            "name": "token",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "holdId",
             "type": {
               "array": [
                 "u8",
@@ -2032,30 +7208,50 @@ export type Payment = {
             }
           },
           {
-            "name": "out",
+            "name": "user",
             "type": "pubkey"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "userTokenAccount",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "feeUser",
+            "name": "mint",
             "type": "pubkey"
           },
           {
-            "name": "amount",
+            "name": "available",
             "type": "u64"
           },
           {
-            "name": "fee",
+            "name": "frozen",
             "type": "u64"
           },
           {
-            "name": "user",
+            "name": "nonce",
+            "type": "u64"
+          },
+          {
+            "name": "wallet",
             "type": "pubkey"
+          },
+          {
+            "name": "exitRequestedAt",
+            "type": "i64"
+          },
+          {
+            "name": "held",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "unfreezeEvent",
+      "name": "walletBoundEvent",
       "type": {
         "kind": "struct",
         "fields": [
@@ -2082,12 +7278,8 @@ export type Payment = {
             "type": "pubkey"
           },
           {
-            "name": "amount",
-            "type": "u64"
-          },
-          {
-            "name": "fee",
-            "type": "u64"
+            "name": "wallet",
+            "type": "pubkey"
           },
           {
             "name": "user",
@@ -2096,26 +7288,6 @@ export type Payment = {
         ]
       }
     },
-    {
-      "name": "userTokenAccount",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "mint",
-            "type": "pubkey"
-          },
-          {
-            "name": "available",
-            "type": "u64"
-          },
-          {
-            "name": "frozen",
-            "type": "u64"
-          }
-        ]
-      }
-    },
     {
       "name": "withdrawEvent",
       "type": {
@@ -2155,6 +7327,10 @@ export type Payment = {
             "name": "frozen",
             "type": "u64"
           },
+          {
+            "name": "received",
+            "type": "u64"
+          },
           {
             "name": "user",
             "type": "pubkey"
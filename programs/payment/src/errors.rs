@@ -44,4 +44,78 @@ pub enum ErrorCode {
     InvalidFeeUser,
     #[msg("Invalid ATA owner")]
     InvalidATAOwner,
+    #[msg("The operation is paused")]
+    Paused,
+    #[msg("Missing transaction record")]
+    MissingRecord,
+    #[msg("Invalid nonce")]
+    InvalidNonce,
+    #[msg("Record is still within its retention window")]
+    RecordNotExpired,
+    #[msg("Invalid Ed25519 signature count")]
+    InvalidSignatureCount,
+    #[msg("Invalid Ed25519 instruction index")]
+    InvalidInstructionIndex,
+    #[msg("Invalid Ed25519 data offset")]
+    InvalidDataOffset,
+    #[msg("Too many quorum signers")]
+    TooManySigners,
+    #[msg("Quorum signer already exists")]
+    SignerAlreadyExists,
+    #[msg("Quorum signer not found")]
+    SignerNotFound,
+    #[msg("Invalid quorum threshold")]
+    InvalidThreshold,
+    #[msg("Insufficient quorum approvals")]
+    InsufficientApprovals,
+    #[msg("Invalid signature scheme")]
+    InvalidSignatureScheme,
+    #[msg("The quorum requires the Ed25519 signature scheme")]
+    QuorumRequiresEd25519,
+    #[msg("Missing Secp256k1 instruction")]
+    MissingSecp256k1Instruction,
+    #[msg("Invalid Secp256k1 instruction")]
+    InvalidSecp256k1Instruction,
+    #[msg("Mint is not enabled")]
+    MintNotEnabled,
+    #[msg("Deposit is below the minimum")]
+    DepositTooSmall,
+    #[msg("Deposit is above the maximum")]
+    DepositTooLarge,
+    #[msg("Account balance cap exceeded")]
+    BalanceCapExceeded,
+    #[msg("Global deposit cap exceeded")]
+    DepositCapExceeded,
+    #[msg("Liabilities exceed the vault balance")]
+    Insolvent,
+    #[msg("Amount exceeds the vault surplus")]
+    InsufficientSurplus,
+    #[msg("SOL payout would leave the vault below its rent-exempt reserve")]
+    VaultRentReserve,
+    #[msg("Exits are disabled")]
+    ExitDisabled,
+    #[msg("No wallet is bound to the account")]
+    WalletNotBound,
+    #[msg("No exit has been requested")]
+    ExitNotRequested,
+    #[msg("Exit delay has not elapsed")]
+    ExitNotReady,
+    #[msg("Missing hold account")]
+    MissingHold,
+    #[msg("Missing native unwrap account")]
+    MissingNativeUnwrap,
+    #[msg("Hold has not reached its release time")]
+    HoldNotReleasable,
+    #[msg("Invalid user token account")]
+    InvalidUserTokenAccount,
+    #[msg("Invalid payee list")]
+    InvalidPayees,
+    #[msg("Invalid batch")]
+    InvalidBatch,
+    #[msg("Invalid recipients")]
+    InvalidRecipients,
+    #[msg("Mint totals have not been seeded")]
+    TotalsNotSeeded,
+    #[msg("Mint totals have already been seeded")]
+    TotalsAlreadySeeded,
 }
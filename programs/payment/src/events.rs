@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{SplitPayee, TransferRecipient};
 
 // #[derive(Debug)]
 #[event]
@@ -7,6 +8,7 @@ pub struct DepositEvent {
     pub account: [u8; 32],
     pub token: Pubkey,
     pub amount: u64,
+    pub received: u64, // Amount that reached the vault and was credited, net of transfer fees
     pub frozen: u64,
     pub user: Pubkey,
 }
@@ -19,6 +21,7 @@ pub struct WithdrawEvent {
     pub to: Pubkey,
     pub available: u64,
     pub frozen: u64,
+    pub received: u64, // Amount delivered to `to`, net of transfer fees
     pub user: Pubkey,
 }
 
@@ -28,6 +31,9 @@ pub struct FreezeEvent {
     pub account: [u8; 32],
     pub token: Pubkey,
     pub amount: u64,
+    pub hold_id: [u8; 32],
+    pub beneficiary: [u8; 32],
+    pub release_at: i64,
     pub user: Pubkey,
 }
 
@@ -38,6 +44,7 @@ pub struct UnfreezeEvent {
     pub token: Pubkey,
     pub amount: u64,
     pub fee: u64,
+    pub hold_id: [u8; 32],
     pub user: Pubkey,
 }
 
@@ -51,6 +58,7 @@ pub struct TransferEvent {
     pub fee_user: Pubkey,
     pub amount: u64,
     pub fee: u64,
+    pub received: u64, // Amount delivered to the recipient, net of transfer fees
     pub user: Pubkey,
 }
 
@@ -68,5 +76,156 @@ pub struct SettleEvent {
     pub fee: u64,
     pub paid: u64,
     pub excess_fee: u64,
+    pub hold_id: [u8; 32],
+    pub received: u64, // Amount delivered to the recipient, net of transfer fees
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct PauseChangedEvent {
+    pub enabled: bool,
+    pub paused: u8,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct FeeToAccountChangedEvent {
+    pub old_fee_to_account: [u8; 32],
+    pub new_fee_to_account: [u8; 32],
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct FeeAccountMigratedEvent {
+    pub token: Pubkey,
+    pub from: [u8; 32],
+    pub to: [u8; 32],
+    pub available: u64,
+    pub frozen: u64,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct MintTotalsSeededEvent {
+    pub token: Pubkey,
+    pub total_available: u64,
+    pub total_frozen: u64,
+    pub total_fees: u64,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct FeeClaimedEvent {
+    pub token: Pubkey,
+    pub from: [u8; 32],
+    pub to: Pubkey,
+    pub amount: u64,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct SolvencyEvent {
+    pub token: Pubkey,
+    pub assets: u64,
+    pub liabilities: u64,
+    pub total_available: u64,
+    pub total_frozen: u64,
+    pub total_fees: u64,
+    pub rent_reserve: u64,
+    pub solvent: bool,
+}
+
+#[event]
+pub struct SurplusSweptEvent {
+    pub token: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub surplus: u64,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct WalletBoundEvent {
+    pub sn: [u8; 32],
+    pub account: [u8; 32],
+    pub token: Pubkey,
+    pub wallet: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct ExitRequestedEvent {
+    pub account: [u8; 32],
+    pub token: Pubkey,
+    pub wallet: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct ExitCancelledEvent {
+    pub sn: [u8; 32],
+    pub account: [u8; 32],
+    pub token: Pubkey,
+    pub wallet: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct ExitExecutedEvent {
+    pub account: [u8; 32],
+    pub token: Pubkey,
+    pub wallet: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub received: u64,
+}
+
+#[event]
+pub struct HoldReleasedEvent {
+    pub hold_id: [u8; 32],
+    pub account: [u8; 32],
+    pub token: Pubkey,
+    pub amount: u64,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct SettleSplitEvent {
+    pub sn: [u8; 32],
+    pub token: Pubkey,
+    pub from: [u8; 32],
+    pub available: u64,
+    pub frozen: u64,
+    pub fee: u64,
+    pub hold_id: [u8; 32],
+    pub payees: Vec<SplitPayee>,
+    pub received: Vec<u64>, // Amount delivered to each payee, net of transfer fees
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct BatchSettleEvent {
+    pub sn: [u8; 32],
+    pub index: u16, // Position of the deal in the batch
+    pub token: Pubkey,
+    pub from: [u8; 32],
+    pub to: [u8; 32],
+    pub available: u64,
+    pub frozen: u64,
+    pub amount: u64,
+    pub fee: u64,
+    pub paid: u64,
+    pub excess_fee: u64,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct TransferManyEvent {
+    pub sn: [u8; 32],
+    pub token: Pubkey,
+    pub from: [u8; 32],
+    pub recipients: Vec<TransferRecipient>,
+    pub total: u64, // Sum credited to the recipients
+    pub fee: u64,
     pub user: Pubkey,
 }
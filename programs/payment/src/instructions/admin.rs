@@ -1,21 +1,46 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, InitializeAccount};
-use crate::{InitializeProgramToken, ChangeOwner};
+use anchor_spl::token;
+use anchor_spl::token_interface::{self, InitializeAccount3};
+use crate::{InitializeProgramToken, SeedMintTotals, ChangeOwner, AcceptRole, AcceptEthSigner, CancelRole, ChangeConfig, ChangeFeeToAccount, MigrateFeeAccount, SetMintConfig};
 use crate::errors::ErrorCode;
+use crate::utils::{get_mint_decimals, rent_reserve, load_user_token_account, verify_secp256k1_instruction};
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::events::{PauseChangedEvent, FeeToAccountChangedEvent, FeeAccountMigratedEvent, MintTotalsSeededEvent};
+use crate::state::{PaymentState, UserTokenAccount, MintConfigParams};
 
 pub fn initialize_program_token(ctx: Context<InitializeProgramToken>) -> Result<()> {
     require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    // Totals created here start at zero, which only matches the ledger of an empty vault
+    let new_totals = ctx.accounts.mint_totals.mint == Pubkey::default();
+    let empty_vault;
     if ctx.accounts.mint.key() == token::spl_token::native_mint::id() {
-        // For SOL, we don't need to do anything extra as the SystemAccount is already initialized
+        // For SOL the SystemAccount is already initialized, only make sure it holds its rent reserve
+        let reserve = rent_reserve(&ctx.accounts.program_token)?;
+        empty_vault = ctx.accounts.program_token.lamports() <= reserve;
+        let shortfall = reserve.saturating_sub(ctx.accounts.program_token.lamports());
+        if shortfall > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.program_token.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        ctx.accounts.mint_totals.rent_reserve = reserve;
     } else {
+        // The mint may belong to the token program or to Token-2022
+        require!(*ctx.accounts.mint.owner == ctx.accounts.token_program.key(), ErrorCode::InvalidMint);
         // Manually initialize the token account
-        token::initialize_account(CpiContext::new_with_signer(
+        token_interface::initialize_account3(CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            InitializeAccount {
+            InitializeAccount3 {
                 account: ctx.accounts.program_token.to_account_info(),
                 mint: ctx.accounts.mint.to_account_info(),
                 authority: ctx.accounts.payment_state.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
             },
             &[&[
                 b"program-token",
@@ -23,24 +48,349 @@ pub fn initialize_program_token(ctx: Context<InitializeProgramToken>) -> Result<
                 &[ctx.bumps.program_token],
             ]],
         ))?;
+        // Initializing fails for a token account that already exists
+        empty_vault = true;
     }
+    if new_totals {
+        ctx.accounts.mint_totals.mint = ctx.accounts.mint.key();
+        ctx.accounts.mint_totals.seeded = empty_vault;
+    }
+    Ok(())
+}
+
+// One-time setup of the totals of a mint whose vault already held deposits when its
+// totals were created. The amounts are the sums over the mint's ledger accounts.
+pub fn seed_mint_totals(ctx: Context<SeedMintTotals>, total_available: u64, total_frozen: u64, total_fees: u64) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(!ctx.accounts.mint_totals.seeded, ErrorCode::TotalsAlreadySeeded);
+    require!(total_fees <= total_available, ErrorCode::InvalidParameter);
+
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    mint_totals.mint = ctx.accounts.mint.key();
+    mint_totals.total_available = total_available;
+    mint_totals.total_frozen = total_frozen;
+    mint_totals.total_fees = total_fees;
+    mint_totals.seeded = true;
+    // Everything owed to the ledger is still in the vault
+    ctx.accounts.mint_config.total_deposits = mint_totals.liabilities();
+
+    emit!(MintTotalsSeededEvent {
+        token: ctx.accounts.mint.key(),
+        total_available,
+        total_frozen,
+        total_fees,
+        user: ctx.accounts.owner.key(),
+    });
     Ok(())
 }
 
 pub fn change_owner(ctx: Context<ChangeOwner>) -> Result<()> {
     require!(ctx.accounts.current_owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
-    ctx.accounts.payment_state.owner = ctx.accounts.new_owner.key();
+    require!(ctx.accounts.new_owner.key() != Pubkey::default(), ErrorCode::InvalidParameter);
+    ctx.accounts.payment_state.pending_owner = ctx.accounts.new_owner.key();
+    Ok(())
+}
+
+pub fn accept_ownership(ctx: Context<AcceptRole>) -> Result<()> {
+    require!(ctx.accounts.new_owner.key() == ctx.accounts.payment_state.pending_owner, ErrorCode::Unauthorized);
+    let payment_state = &mut ctx.accounts.payment_state;
+    payment_state.owner = payment_state.pending_owner;
+    payment_state.pending_owner = Pubkey::default();
+    Ok(())
+}
+
+pub fn cancel_ownership(ctx: Context<CancelRole>) -> Result<()> {
+    require!(ctx.accounts.current_owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    ctx.accounts.payment_state.pending_owner = Pubkey::default();
     Ok(())
 }
 
 pub fn change_signer(ctx: Context<ChangeOwner>) -> Result<()> {
     require!(ctx.accounts.current_owner.key() == ctx.accounts.payment_state.owner || ctx.accounts.current_owner.key() == ctx.accounts.payment_state.signer, ErrorCode::Unauthorized);
-    ctx.accounts.payment_state.signer = ctx.accounts.new_owner.key();
+    require!(ctx.accounts.new_owner.key() != Pubkey::default(), ErrorCode::InvalidParameter);
+    ctx.accounts.payment_state.pending_signer = ctx.accounts.new_owner.key();
+    Ok(())
+}
+
+pub fn accept_signer(ctx: Context<AcceptRole>) -> Result<()> {
+    require!(ctx.accounts.new_owner.key() == ctx.accounts.payment_state.pending_signer, ErrorCode::Unauthorized);
+    let clock = Clock::get()?;
+    let payment_state = &mut ctx.accounts.payment_state;
+    // Keep accepting the old signer for a while so in-flight signed messages still execute
+    payment_state.previous_signer = payment_state.signer;
+    payment_state.previous_valid_until = clock.unix_timestamp.checked_add(payment_state.signer_overlap).ok_or(ErrorCode::InvalidParameter)?;
+    payment_state.signer = payment_state.pending_signer;
+    payment_state.pending_signer = Pubkey::default();
+    Ok(())
+}
+
+pub fn cancel_signer(ctx: Context<CancelRole>) -> Result<()> {
+    require!(ctx.accounts.current_owner.key() == ctx.accounts.payment_state.owner || ctx.accounts.current_owner.key() == ctx.accounts.payment_state.signer, ErrorCode::Unauthorized);
+    ctx.accounts.payment_state.pending_signer = Pubkey::default();
     Ok(())
 }
 
 pub fn change_fee_to(ctx: Context<ChangeOwner>) -> Result<()> {
     require!(ctx.accounts.current_owner.key() == ctx.accounts.payment_state.owner || ctx.accounts.current_owner.key() == ctx.accounts.payment_state.fee_to, ErrorCode::Unauthorized);
-    ctx.accounts.payment_state.fee_to = ctx.accounts.new_owner.key();
+    require!(ctx.accounts.new_owner.key() != Pubkey::default(), ErrorCode::InvalidParameter);
+    ctx.accounts.payment_state.pending_fee_to = ctx.accounts.new_owner.key();
+    Ok(())
+}
+
+pub fn accept_fee_to(ctx: Context<AcceptRole>) -> Result<()> {
+    require!(ctx.accounts.new_owner.key() == ctx.accounts.payment_state.pending_fee_to, ErrorCode::Unauthorized);
+    let payment_state = &mut ctx.accounts.payment_state;
+    payment_state.fee_to = payment_state.pending_fee_to;
+    payment_state.pending_fee_to = Pubkey::default();
+    Ok(())
+}
+
+pub fn cancel_fee_to(ctx: Context<CancelRole>) -> Result<()> {
+    require!(ctx.accounts.current_owner.key() == ctx.accounts.payment_state.owner || ctx.accounts.current_owner.key() == ctx.accounts.payment_state.fee_to, ErrorCode::Unauthorized);
+    ctx.accounts.payment_state.pending_fee_to = Pubkey::default();
+    Ok(())
+}
+
+pub fn set_enabled(ctx: Context<ChangeConfig>, enabled: bool) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    let payment_state = &mut ctx.accounts.payment_state;
+    payment_state.enabled = enabled;
+
+    emit!(PauseChangedEvent {
+        enabled: payment_state.enabled,
+        paused: payment_state.paused,
+        user: ctx.accounts.owner.key(),
+    });
+    Ok(())
+}
+
+pub fn set_paused(ctx: Context<ChangeConfig>, paused: u8) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(paused & !PaymentState::PAUSE_ALL == 0, ErrorCode::InvalidParameter);
+    let payment_state = &mut ctx.accounts.payment_state;
+    payment_state.paused = paused;
+
+    emit!(PauseChangedEvent {
+        enabled: payment_state.enabled,
+        paused: payment_state.paused,
+        user: ctx.accounts.owner.key(),
+    });
+    Ok(())
+}
+
+pub fn set_nosn_enabled(ctx: Context<ChangeConfig>, enabled: bool) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    ctx.accounts.payment_state.nosn_enabled = enabled;
+    Ok(())
+}
+
+pub fn set_record_retention(ctx: Context<ChangeConfig>, retention: i64) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(retention >= 0, ErrorCode::InvalidParameter);
+    ctx.accounts.payment_state.record_retention = retention;
+    Ok(())
+}
+
+// Switches the fee ledger to a new account id. The fee ledgers of the mints passed in
+// remaining_accounts as (mint, old fee ledger, new fee ledger) are migrated right away,
+// any other mint can be migrated later with migrate_fee_account.
+pub fn change_fee_to_account<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ChangeFeeToAccount<'info>>,
+    fee_to_account: [u8; 32],
+) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(fee_to_account != [0u8; 32], ErrorCode::InvalidParameter);
+    let migrations = ctx.remaining_accounts.chunks_exact(3);
+    require!(migrations.remainder().is_empty(), ErrorCode::InvalidParameter);
+    let payment_state = &mut ctx.accounts.payment_state;
+    let old_fee_to_account = payment_state.fee_to_account;
+    require!(fee_to_account != old_fee_to_account, ErrorCode::InvalidParameter);
+    payment_state.previous_fee_to_account = old_fee_to_account;
+    payment_state.fee_to_account = fee_to_account;
+
+    emit!(FeeToAccountChangedEvent {
+        old_fee_to_account,
+        new_fee_to_account: fee_to_account,
+        user: ctx.accounts.owner.key(),
+    });
+
+    for accounts in migrations {
+        let mint = accounts[0].key();
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"user-token", old_fee_to_account.as_ref(), mint.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(accounts[1].key(), expected, ErrorCode::InvalidUserTokenAccount);
+        let mut old_fee_token_account = Account::<UserTokenAccount>::try_from(&accounts[1])?;
+        let mut fee_token_account = load_user_token_account(
+            &accounts[2],
+            &fee_to_account,
+            &mint,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        migrate_fee_ledger(&mut old_fee_token_account, &mut fee_token_account, mint, old_fee_to_account, fee_to_account, ctx.accounts.owner.key())?;
+        old_fee_token_account.exit(&crate::ID)?;
+        fee_token_account.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+// Moves the fee ledger balance of one mint from the previous fee_to_account to the current one,
+// for mints that were not migrated by change_fee_to_account.
+pub fn migrate_fee_account(ctx: Context<MigrateFeeAccount>) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    let payment_state = &ctx.accounts.payment_state;
+    require!(payment_state.previous_fee_to_account != [0u8; 32], ErrorCode::InvalidParameter);
+    if ctx.accounts.fee_token_account.mint == Pubkey::default() {
+        ctx.accounts.fee_token_account.mint = ctx.accounts.mint.key();
+    }
+    migrate_fee_ledger(
+        &mut ctx.accounts.old_fee_token_account,
+        &mut ctx.accounts.fee_token_account,
+        ctx.accounts.mint.key(),
+        payment_state.previous_fee_to_account,
+        payment_state.fee_to_account,
+        ctx.accounts.owner.key(),
+    )
+}
+
+fn migrate_fee_ledger(
+    old_fee_token_account: &mut UserTokenAccount,
+    fee_token_account: &mut UserTokenAccount,
+    mint: Pubkey,
+    from: [u8; 32],
+    to: [u8; 32],
+    user: Pubkey,
+) -> Result<()> {
+    let available = old_fee_token_account.available;
+    let frozen = old_fee_token_account.frozen;
+    old_fee_token_account.available = 0;
+    old_fee_token_account.frozen = 0;
+
+    fee_token_account.available = fee_token_account.available.checked_add(available).ok_or(ErrorCode::InvalidAmount)?;
+    fee_token_account.frozen = fee_token_account.frozen.checked_add(frozen).ok_or(ErrorCode::InvalidAmount)?;
+
+    emit!(FeeAccountMigratedEvent {
+        token: mint,
+        from,
+        to,
+        available,
+        frozen,
+        user,
+    });
+    Ok(())
+}
+
+pub fn add_quorum_signer(ctx: Context<ChangeConfig>, signer: Pubkey) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(signer != Pubkey::default(), ErrorCode::InvalidParameter);
+    let payment_state = &mut ctx.accounts.payment_state;
+    require!(!payment_state.quorum_signers.contains(&signer), ErrorCode::SignerAlreadyExists);
+    require!(payment_state.quorum_signers.len() < PaymentState::MAX_QUORUM_SIGNERS, ErrorCode::TooManySigners);
+    payment_state.quorum_signers.push(signer);
+    Ok(())
+}
+
+pub fn remove_quorum_signer(ctx: Context<ChangeConfig>, signer: Pubkey) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    let payment_state = &mut ctx.accounts.payment_state;
+    let index = payment_state.quorum_signers.iter().position(|s| *s == signer).ok_or(ErrorCode::SignerNotFound)?;
+    payment_state.quorum_signers.remove(index);
+    // The threshold must stay reachable with the remaining signers
+    require!(payment_state.quorum_threshold as usize <= payment_state.quorum_signers.len(), ErrorCode::InvalidThreshold);
+    Ok(())
+}
+
+pub fn set_quorum_threshold(ctx: Context<ChangeConfig>, threshold: u8) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    let payment_state = &mut ctx.accounts.payment_state;
+    require!(threshold as usize <= payment_state.quorum_signers.len(), ErrorCode::InvalidThreshold);
+    require!(threshold == 0 || payment_state.signature_scheme == PaymentState::SCHEME_ED25519, ErrorCode::QuorumRequiresEd25519);
+    payment_state.quorum_threshold = threshold;
+    Ok(())
+}
+
+pub fn set_signer_overlap(ctx: Context<ChangeConfig>, overlap: i64) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!((0..=PaymentState::MAX_SIGNER_OVERLAP).contains(&overlap), ErrorCode::InvalidParameter);
+    ctx.accounts.payment_state.signer_overlap = overlap;
+    Ok(())
+}
+
+pub fn set_exit_delay(ctx: Context<ChangeConfig>, delay: i64) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(
+        delay == 0 || (PaymentState::MIN_EXIT_DELAY..=PaymentState::MAX_EXIT_DELAY).contains(&delay),
+        ErrorCode::InvalidParameter
+    );
+    ctx.accounts.payment_state.exit_delay = delay;
+    Ok(())
+}
+
+pub fn change_eth_signer(ctx: Context<ChangeConfig>, eth_signer: [u8; 20]) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(eth_signer != [0u8; 20], ErrorCode::InvalidParameter);
+    ctx.accounts.payment_state.pending_eth_signer = eth_signer;
+    Ok(())
+}
+
+// The pending address accepts by signing the AcceptEthSigner message, which proves the
+// backend holds its key before any secp256k1 signature depends on it
+pub fn accept_eth_signer(ctx: Context<AcceptEthSigner>, signature: [u8; 64]) -> Result<()> {
+    let pending = ctx.accounts.payment_state.pending_eth_signer;
+    require!(pending != [0u8; 20], ErrorCode::InvalidParameter);
+    // Accepting clears the pending address, so the message cannot be replayed
+    let message = build_message(Operation::AcceptEthSigner, ReplayMode::Record, &[&pending]);
+    verify_secp256k1_instruction(&ctx.accounts.instruction_sysvar, &[pending], &message, &signature)?;
+
+    let clock = Clock::get()?;
+    let payment_state = &mut ctx.accounts.payment_state;
+    payment_state.previous_eth_signer = payment_state.eth_signer;
+    payment_state.previous_eth_valid_until = clock.unix_timestamp.checked_add(payment_state.signer_overlap).ok_or(ErrorCode::InvalidParameter)?;
+    payment_state.eth_signer = pending;
+    payment_state.pending_eth_signer = [0u8; 20];
+    Ok(())
+}
+
+pub fn cancel_eth_signer(ctx: Context<ChangeConfig>) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    ctx.accounts.payment_state.pending_eth_signer = [0u8; 20];
+    Ok(())
+}
+
+pub fn set_signature_scheme(ctx: Context<ChangeConfig>, scheme: u8) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    let payment_state = &mut ctx.accounts.payment_state;
+    match scheme {
+        PaymentState::SCHEME_ED25519 => {}
+        PaymentState::SCHEME_SECP256K1 => {
+            require!(payment_state.eth_signer != [0u8; 20], ErrorCode::InvalidParameter);
+            require!(payment_state.quorum_threshold == 0, ErrorCode::QuorumRequiresEd25519);
+        }
+        _ => return Err(ErrorCode::InvalidSignatureScheme.into()),
+    }
+    payment_state.signature_scheme = scheme;
+    Ok(())
+}
+
+pub fn set_mint_config(ctx: Context<SetMintConfig>, params: MintConfigParams) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(params.max_deposit == 0 || params.min_deposit <= params.max_deposit, ErrorCode::InvalidParameter);
+
+    let decimals = if ctx.accounts.mint.key() == token::spl_token::native_mint::id() {
+        9
+    } else {
+        get_mint_decimals(&ctx.accounts.mint, &ctx.accounts.token_program)?
+    };
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.mint = ctx.accounts.mint.key();
+    mint_config.decimals = decimals;
+    mint_config.enabled = params.enabled;
+    mint_config.min_deposit = params.min_deposit;
+    mint_config.max_deposit = params.max_deposit;
+    mint_config.max_balance = params.max_balance;
+    mint_config.deposit_cap = params.deposit_cap;
     Ok(())
 }
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::utils::vault_assets;
+use crate::errors::ErrorCode;
+use crate::events::SolvencyEvent;
+use crate::CheckSolvency;
+
+pub fn handler(ctx: Context<CheckSolvency>) -> Result<()> {
+    let assets = vault_assets(&ctx.accounts.mint, &ctx.accounts.program_token)?;
+
+    let mint_totals = &ctx.accounts.mint_totals;
+    let liabilities = mint_totals.liabilities();
+    let solvent = assets >= liabilities;
+
+    emit!(SolvencyEvent {
+        token: ctx.accounts.mint.key(),
+        assets,
+        liabilities,
+        total_available: mint_totals.total_available,
+        total_frozen: mint_totals.total_frozen,
+        total_fees: mint_totals.total_fees,
+        rent_reserve: mint_totals.rent_reserve,
+        solvent,
+    });
+
+    if !solvent {
+        msg!("Insolvent: liabilities {} exceed assets {}", liabilities, assets);
+        return Err(ErrorCode::Insolvent.into());
+    }
+    Ok(())
+}
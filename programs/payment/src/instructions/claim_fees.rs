@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::utils::{transfer_sol, transfer_token};
+use crate::errors::ErrorCode;
+use crate::events::FeeClaimedEvent;
+use crate::ClaimFees;
+
+pub fn handler(ctx: Context<ClaimFees>, amount: u64) -> Result<()> {
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(ctx.accounts.fee_to.key() == ctx.accounts.payment_state.fee_to, ErrorCode::Unauthorized);
+    require!(amount > 0, ErrorCode::ZeroAmount);
+
+    let fee_token_account = &mut ctx.accounts.fee_token_account;
+    require!(fee_token_account.available >= amount, ErrorCode::InsufficientAvailable);
+    fee_token_account.available = fee_token_account.available.checked_sub(amount).unwrap();
+    ctx.accounts.mint_config.sub_deposits(amount)?;
+    ctx.accounts.mint_totals.sub_fees(amount)?;
+
+    if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
+        transfer_sol(
+            &ctx.accounts.program_token,
+            &ctx.accounts.to,
+            &ctx.accounts.system_program,
+            ctx.bumps.program_token,
+            amount,
+        )?;
+    } else {
+        transfer_token(
+            &ctx.accounts.program_token,
+            &ctx.accounts.to,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.payment_state,
+            ctx.bumps.payment_state,
+            amount,
+        )?;
+    }
+
+    emit!(FeeClaimedEvent {
+        token: ctx.accounts.mint.key(),
+        from: ctx.accounts.payment_state.fee_to_account,
+        to: ctx.accounts.to.key(),
+        amount,
+        user: ctx.accounts.fee_to.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::state::TransactionRecord;
+use crate::CloseRecords;
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseRecords<'info>>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    require!(
+        authority == ctx.accounts.payer.key() || authority == ctx.accounts.payment_state.owner,
+        ErrorCode::Unauthorized
+    );
+    require!(!ctx.remaining_accounts.is_empty(), ErrorCode::InvalidParameter);
+
+    let clock = Clock::get()?;
+    let retention = ctx.accounts.payment_state.record_retention;
+
+    for record_info in ctx.remaining_accounts.iter() {
+        if record_info.data_len() == 8 + TransactionRecord::LEGACY_LEN {
+            close_legacy_record(&ctx, record_info)?;
+            continue;
+        }
+
+        let record = Account::<TransactionRecord>::try_from(record_info)?;
+        require!(record.executed, ErrorCode::InvalidParameter);
+        require!(record.payer == ctx.accounts.payer.key(), ErrorCode::Unauthorized);
+
+        // Once the signed message has expired the sn can no longer be replayed,
+        // so the record is only kept for the configured retention window
+        let closable_at = record.expired_at.checked_add(retention).ok_or(ErrorCode::InvalidParameter)?;
+        require!(clock.unix_timestamp >= closable_at, ErrorCode::RecordNotExpired);
+
+        record.close(ctx.accounts.payer.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+// Records of the old layout carry neither an expiry nor their payer. Every signature they
+// guarded predates the versioned message envelope and can no longer be replayed, so the
+// owner may close them and send the rent to the designated `payer` account.
+fn close_legacy_record<'info>(ctx: &Context<'_, '_, 'info, 'info, CloseRecords<'info>>, record_info: &AccountInfo<'info>) -> Result<()> {
+    require_keys_eq!(ctx.accounts.authority.key(), ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require_keys_eq!(*record_info.owner, crate::ID, ErrorCode::InvalidParameter);
+    require!(
+        record_info.try_borrow_data()?.get(..8) == Some(&<TransactionRecord as anchor_lang::Discriminator>::DISCRIMINATOR[..]),
+        ErrorCode::InvalidParameter
+    );
+
+    let payer = ctx.accounts.payer.to_account_info();
+    **payer.try_borrow_mut_lamports()? = payer.lamports().checked_add(record_info.lamports()).unwrap();
+    **record_info.try_borrow_mut_lamports()? = 0;
+    record_info.assign(&System::id());
+    record_info.realloc(0, false)?;
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, TokenAccount};
-use crate::utils::verify_ed25519_instruction;
+use anchor_spl::token_interface::{self, CloseAccount, InitializeAccount3, TokenAccount, TransferChecked};
+use crate::utils::{verify_signature, consume_sn, get_mint_decimals, create_pda_account};
 use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::state::PaymentState;
 use crate::events::DepositEvent;
 use crate::Deposit;
 
@@ -15,46 +17,69 @@ pub fn handler(
     signature: [u8; 64],
 ) -> Result<()> {
     require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_DEPOSIT), ErrorCode::Paused);
     require!(amount > 0 || frozen > 0, ErrorCode::ZeroAmount);
 
     let clock = Clock::get()?;
     require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
 
-    let message = [
+    let message = build_message(Operation::Deposit, ReplayMode::of(&ctx.accounts.record), &[
         &sn[..], &account[..], &amount.to_le_bytes(), &frozen.to_le_bytes(), &expired_at.to_le_bytes(),
         &ctx.accounts.user.key().to_bytes()[..],
         &ctx.accounts.mint.key().to_bytes()[..],
         &ctx.accounts.user_token.key().to_bytes()[..],
-    ].concat();
-    verify_ed25519_instruction(
+    ]);
+    verify_signature(
         &ctx.accounts.instruction_sysvar,
-        ctx.accounts.payment_state.signer.as_ref(),
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
         &message,
         &signature,
     )?;
     
     // Check if the transaction has already been executed
-    require!(!ctx.accounts.record.executed, ErrorCode::AlreadyExecuted);
-    ctx.accounts.record.executed = true;
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.user_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
 
-    // Transfer tokens
+    // Transfer tokens, crediting what actually reached the vault
+    let mut received = 0;
     if amount > 0 { 
+      ctx.accounts.mint_config.check_deposit(amount)?;
       if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
+          if *ctx.accounts.user_token.owner == ctx.accounts.token_program.key() {
+              _unwrap_native(&ctx, amount)?;
+          }
           _handler_sol(&ctx, amount)?;
+          received = amount;
       } else {
-          _handler_token(&ctx, amount)?;
+          received = _handler_token(&ctx, amount)?;
       }
     }
 
     // Update user_token_account
     let user_token_account = &mut ctx.accounts.user_token_account;
-    user_token_account.available = user_token_account.available.checked_add(amount).unwrap();
+    user_token_account.available = user_token_account.available.checked_add(received).unwrap();
     user_token_account.available = user_token_account.available.checked_sub(frozen).ok_or(ErrorCode::InsufficientAvailable)?;
     user_token_account.frozen = user_token_account.frozen.checked_add(frozen).unwrap();
     if user_token_account.mint == Pubkey::default() {
         user_token_account.mint = ctx.accounts.mint.key();
     }
 
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.check_balance(user_token_account)?;
+    mint_config.total_deposits = mint_config.total_deposits.checked_add(received).unwrap();
+
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    mint_totals.add_available(received)?;
+    mint_totals.sub_available(frozen)?;
+    mint_totals.add_frozen(frozen)?;
+
     // msg!("Emitting DepositEvent: {:?}", DepositEvent { sn, account, token: ctx.accounts.mint.key(), amount, frozen, user: ctx.accounts.user.key() });
     // Emit the deposit event
     emit!(DepositEvent {
@@ -62,6 +87,7 @@ pub fn handler(
         account,
         token: ctx.accounts.mint.key(),
         amount,
+        received,
         frozen,
         user: ctx.accounts.user.key(),
     });
@@ -88,25 +114,92 @@ pub fn _handler_sol(
     Ok(())
 }
 
-pub fn _handler_token(
+// Moves `amount` out of the user's wSOL account and unwraps it into the user's
+// lamports, so the deposit then proceeds as a plain SOL transfer
+pub fn _unwrap_native(
     ctx: &Context<Deposit>,
     amount: u64,
 ) -> Result<()> {
+    require!(*ctx.accounts.mint.owner == ctx.accounts.token_program.key(), ErrorCode::InvalidMint);
+    let user_token = TokenAccount::try_deserialize(&mut &ctx.accounts.user_token.data.borrow()[..])?;
+    require!(user_token.mint == ctx.accounts.mint.key(), ErrorCode::InvalidMint);
+
+    let native_unwrap = ctx.accounts.native_unwrap.as_ref().ok_or(ErrorCode::MissingNativeUnwrap)?.to_account_info();
+    let unwrap_bump = ctx.bumps.native_unwrap.ok_or(ErrorCode::MissingNativeUnwrap)?;
+    let user_key = ctx.accounts.user.key();
+    let unwrap_seeds: &[&[u8]] = &[b"native-unwrap", user_key.as_ref(), &[unwrap_bump]];
+    let state_seeds: &[&[u8]] = &[b"payment-state", &[ctx.bumps.payment_state]];
+
+    create_pda_account(
+        &native_unwrap,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        anchor_spl::token::TokenAccount::LEN,
+        ctx.accounts.token_program.key,
+        unwrap_seeds,
+    )?;
+    token_interface::initialize_account3(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        InitializeAccount3 {
+            account: native_unwrap.clone(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.payment_state.to_account_info(),
+        },
+    ))?;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: native_unwrap.clone(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+        anchor_spl::token::spl_token::native_mint::DECIMALS,
+    )?;
+
+    // Closing hands the unwrapped lamports and the rent back to the user
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: native_unwrap,
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.payment_state.to_account_info(),
+        },
+        &[state_seeds],
+    ))?;
+    Ok(())
+}
+
+// Returns the amount credited to the vault, which is less than `amount` when a
+// Token-2022 transfer fee is withheld
+pub fn _handler_token(
+    ctx: &Context<Deposit>,
+    amount: u64,
+) -> Result<u64> {
     // Check if the program token is initialized
     let program_token = TokenAccount::try_deserialize(&mut &ctx.accounts.program_token.data.borrow()[..])?;
     require!(program_token.mint == ctx.accounts.mint.key(), ErrorCode::InvalidProgramToken);
 
     // Token transfer
-    token::transfer(
+    let decimals = get_mint_decimals(&ctx.accounts.mint, &ctx.accounts.token_program)?;
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            TransferChecked {
                 from: ctx.accounts.user_token.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.program_token.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         amount,
+        decimals,
     )?;
-    Ok(())
+
+    let program_token_after = TokenAccount::try_deserialize(&mut &ctx.accounts.program_token.data.borrow()[..])?;
+    Ok(program_token_after.amount.checked_sub(program_token.amount).unwrap())
 }
\ No newline at end of file
@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use crate::utils::{verify_signature, consume_sn, transfer_sol, transfer_token, sync_if_wrapped, get_ata_owner_from_unchecked_account};
+use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::events::{WalletBoundEvent, ExitRequestedEvent, ExitCancelledEvent, ExitExecutedEvent};
+use crate::{BindWallet, RequestExit, CancelExit, ExecuteExit};
+
+// The backend vouches once for the wallet behind an account; from then on the wallet
+// can pull its available balance through request_exit/execute_exit on its own.
+pub fn bind_wallet(
+    ctx: Context<BindWallet>,
+    sn: [u8; 32],
+    account: [u8; 32],
+    wallet: Pubkey,
+    expired_at: i64,
+    signature: [u8; 64],
+) -> Result<()> {
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(wallet != Pubkey::default(), ErrorCode::InvalidParameter);
+
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
+
+    let message = build_message(Operation::BindWallet, ReplayMode::of(&ctx.accounts.record), &[
+        &sn[..], &account[..], &wallet.to_bytes()[..], &expired_at.to_le_bytes(),
+        &ctx.accounts.user.key().to_bytes()[..],
+        &ctx.accounts.mint.key().to_bytes()[..],
+    ]);
+    verify_signature(
+        &ctx.accounts.instruction_sysvar,
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
+        &message,
+        &signature,
+    )?;
+
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.user_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
+
+    let user_token_account = &mut ctx.accounts.user_token_account;
+    if user_token_account.mint == Pubkey::default() {
+        user_token_account.mint = ctx.accounts.mint.key();
+    }
+    // A new wallet must start its own exit request
+    user_token_account.wallet = wallet;
+    user_token_account.exit_requested_at = 0;
+
+    emit!(WalletBoundEvent {
+        sn,
+        account,
+        token: ctx.accounts.mint.key(),
+        wallet,
+        user: ctx.accounts.user.key(),
+    });
+    Ok(())
+}
+
+// Exits deliberately ignore `enabled` and the pause flags: they exist for when the
+// backend cannot act, and only the owner-set delay governs them.
+pub fn request_exit(ctx: Context<RequestExit>, account: [u8; 32]) -> Result<()> {
+    let exit_delay = ctx.accounts.payment_state.exit_delay;
+    require!(exit_delay > 0, ErrorCode::ExitDisabled);
+
+    let user_token_account = &mut ctx.accounts.user_token_account;
+    require!(user_token_account.wallet != Pubkey::default(), ErrorCode::WalletNotBound);
+    require!(user_token_account.wallet == ctx.accounts.wallet.key(), ErrorCode::Unauthorized);
+
+    let clock = Clock::get()?;
+    user_token_account.exit_requested_at = clock.unix_timestamp;
+
+    emit!(ExitRequestedEvent {
+        account,
+        token: ctx.accounts.mint.key(),
+        wallet: ctx.accounts.wallet.key(),
+        executable_at: clock.unix_timestamp.checked_add(exit_delay).ok_or(ErrorCode::InvalidParameter)?,
+    });
+    Ok(())
+}
+
+// The backend vetoes a pending exit, e.g. when the wallet is known to be compromised.
+// The wallet can request a new exit, which starts a new delay.
+pub fn cancel_exit(
+    ctx: Context<CancelExit>,
+    sn: [u8; 32],
+    account: [u8; 32],
+    expired_at: i64,
+    signature: [u8; 64],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
+
+    let message = build_message(Operation::CancelExit, ReplayMode::of(&ctx.accounts.record), &[
+        &sn[..], &account[..], &expired_at.to_le_bytes(),
+        &ctx.accounts.user.key().to_bytes()[..],
+        &ctx.accounts.mint.key().to_bytes()[..],
+    ]);
+    verify_signature(
+        &ctx.accounts.instruction_sysvar,
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
+        &message,
+        &signature,
+    )?;
+
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.user_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
+
+    let user_token_account = &mut ctx.accounts.user_token_account;
+    require!(user_token_account.exit_requested_at > 0, ErrorCode::ExitNotRequested);
+    user_token_account.exit_requested_at = 0;
+
+    emit!(ExitCancelledEvent {
+        sn,
+        account,
+        token: ctx.accounts.mint.key(),
+        wallet: user_token_account.wallet,
+        user: ctx.accounts.user.key(),
+    });
+    Ok(())
+}
+
+pub fn execute_exit(ctx: Context<ExecuteExit>, account: [u8; 32]) -> Result<()> {
+    let exit_delay = ctx.accounts.payment_state.exit_delay;
+    require!(exit_delay > 0, ErrorCode::ExitDisabled);
+
+    let wallet = ctx.accounts.wallet.key();
+    let user_token_account = &mut ctx.accounts.user_token_account;
+    require!(user_token_account.wallet != Pubkey::default(), ErrorCode::WalletNotBound);
+    require!(user_token_account.wallet == wallet, ErrorCode::Unauthorized);
+    require!(user_token_account.exit_requested_at > 0, ErrorCode::ExitNotRequested);
+
+    let clock = Clock::get()?;
+    let executable_at = user_token_account.exit_requested_at.checked_add(exit_delay).ok_or(ErrorCode::InvalidParameter)?;
+    require!(clock.unix_timestamp >= executable_at, ErrorCode::ExitNotReady);
+
+    // Funds only ever leave to the bound wallet or a token account it owns
+    let is_native = ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id();
+    let to_owner = if is_native && *ctx.accounts.to.owner != ctx.accounts.token_program.key() {
+        ctx.accounts.to.key()
+    } else {
+        get_ata_owner_from_unchecked_account(&ctx.accounts.to)?
+    };
+    require!(to_owner == wallet, ErrorCode::Unauthorized);
+
+    let amount = user_token_account.available;
+    require!(amount > 0, ErrorCode::ZeroAmount);
+    user_token_account.available = 0;
+    user_token_account.exit_requested_at = 0;
+
+    ctx.accounts.mint_totals.sub_available(amount)?;
+    ctx.accounts.mint_config.sub_deposits(amount)?;
+
+    let received = if is_native {
+        transfer_sol(
+            &ctx.accounts.program_token,
+            &ctx.accounts.to,
+            &ctx.accounts.system_program,
+            ctx.bumps.program_token,
+            amount,
+        )?;
+        sync_if_wrapped(&ctx.accounts.to, &ctx.accounts.token_program)?;
+        amount
+    } else {
+        transfer_token(
+            &ctx.accounts.program_token,
+            &ctx.accounts.to,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.payment_state,
+            ctx.bumps.payment_state,
+            amount,
+        )?
+    };
+
+    emit!(ExitExecutedEvent {
+        account,
+        token: ctx.accounts.mint.key(),
+        wallet,
+        to: ctx.accounts.to.key(),
+        amount,
+        received,
+    });
+    Ok(())
+}
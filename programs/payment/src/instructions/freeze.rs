@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::utils::verify_ed25519_instruction;
+use crate::utils::{verify_signature, consume_sn};
 use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::state::PaymentState;
 use crate::events::FreezeEvent;
 use crate::Freeze;
 
@@ -9,30 +11,43 @@ pub fn handler(
     sn: [u8; 32],
     account: [u8; 32],
     amount: u64,
+    hold_id: [u8; 32],
+    beneficiary: [u8; 32],
+    release_at: i64,
     expired_at: i64,
     signature: [u8; 64],
 ) -> Result<()> {
     require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_FREEZE), ErrorCode::Paused);
     require!(amount > 0, ErrorCode::ZeroAmount);
 
     let clock = Clock::get()?;
     require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
 
-    let message = [ 
-        &sn[..], &account[..], &amount.to_le_bytes(), &expired_at.to_le_bytes(),
+    let message = build_message(Operation::Freeze, ReplayMode::of(&ctx.accounts.record), &[
+        &sn[..], &account[..], &amount.to_le_bytes(),
+        &hold_id[..], &beneficiary[..], &release_at.to_le_bytes(),
+        &expired_at.to_le_bytes(),
         &ctx.accounts.user.key().to_bytes()[..],
         &ctx.accounts.mint.key().to_bytes()[..],
-    ].concat();
-    verify_ed25519_instruction(
+    ]);
+    verify_signature(
         &ctx.accounts.instruction_sysvar,
-        ctx.accounts.payment_state.signer.as_ref(),
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
         &message,
         &signature,
     )?;
     
     // Check if the transaction has already been executed
-    require!(!ctx.accounts.record.executed, ErrorCode::AlreadyExecuted);
-    ctx.accounts.record.executed = true;
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.user_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
 
     // Check if the user has enough available balance
     let user_token_account = &mut ctx.accounts.user_token_account;
@@ -42,12 +57,34 @@ pub fn handler(
     user_token_account.available -= amount;
     user_token_account.frozen += amount;
 
+    // A non-zero hold_id reserves the frozen funds for one order until release_at
+    if hold_id != [0u8; 32] {
+        require!(release_at > clock.unix_timestamp, ErrorCode::InvalidParameter);
+        let hold = ctx.accounts.hold.as_mut().ok_or(ErrorCode::MissingHold)?;
+        hold.account = account;
+        hold.mint = ctx.accounts.mint.key();
+        hold.amount = amount;
+        hold.beneficiary = beneficiary;
+        hold.release_at = release_at;
+        hold.payer = ctx.accounts.user.key();
+        user_token_account.held = user_token_account.held.checked_add(amount).ok_or(ErrorCode::InvalidAmount)?;
+    } else {
+        require!(ctx.accounts.hold.is_none(), ErrorCode::InvalidParameter);
+    }
+
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    mint_totals.sub_available(amount)?;
+    mint_totals.add_frozen(amount)?;
+
     // Emit the freeze event
     emit!(FreezeEvent {
         sn,
         account,
         token: ctx.accounts.mint.key(),
         amount,
+        hold_id,
+        beneficiary,
+        release_at,
         user: ctx.accounts.user.key(),
     });
     Ok(())
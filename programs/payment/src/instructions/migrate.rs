@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::utils::grow_account;
+use crate::state::{PaymentState, UserTokenAccount};
+use crate::{MigratePaymentState, MigrateUserTokenAccount};
+
+// Grows the payment state to the current layout after an upgrade appended fields to it.
+// Its data may not deserialize yet, so only the discriminator and owner are read.
+pub fn migrate_payment_state(ctx: Context<MigratePaymentState>) -> Result<()> {
+    let payment_state = ctx.accounts.payment_state.to_account_info();
+    check_account(&payment_state, &<PaymentState as anchor_lang::Discriminator>::DISCRIMINATOR)?;
+    // The owner is the first field of every layout
+    require!(
+        payment_state.try_borrow_data()?.get(8..40) == Some(&ctx.accounts.owner.key().to_bytes()[..]),
+        ErrorCode::Unauthorized
+    );
+
+    grow_account(
+        &payment_state,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + PaymentState::LEN,
+    )
+}
+
+// Grows a user-token account created before nonce, wallet and hold tracking were added to
+// the ledger. Anyone may pay for it, the balances are left as they are.
+pub fn migrate_user_token_account(ctx: Context<MigrateUserTokenAccount>, _account: [u8; 32]) -> Result<()> {
+    let user_token_account = ctx.accounts.user_token_account.to_account_info();
+    check_account(&user_token_account, &<UserTokenAccount as anchor_lang::Discriminator>::DISCRIMINATOR)?;
+
+    grow_account(
+        &user_token_account,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + UserTokenAccount::LEN,
+    )
+}
+
+// The account must be one of ours holding the given type, whatever its layout version
+fn check_account(info: &AccountInfo, discriminator: &[u8]) -> Result<()> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidParameter);
+    require!(info.try_borrow_data()?.get(..8) == Some(discriminator), ErrorCode::InvalidParameter);
+    Ok(())
+}
@@ -5,4 +5,14 @@ pub mod withdraw;
 pub mod freeze;
 pub mod unfreeze;
 pub mod transfer;
-pub mod settle;
\ No newline at end of file
+pub mod settle;
+pub mod close_records;
+pub mod claim_fees;
+pub mod check_solvency;
+pub mod sweep_surplus;
+pub mod exit;
+pub mod release_hold;
+pub mod settle_split;
+pub mod settle_batch;
+pub mod transfer_many;
+pub mod migrate;
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::events::HoldReleasedEvent;
+use crate::ReleaseHold;
+
+// Permissionless: once a hold outlives its release time without being settled,
+// anyone may return what is left of it to the account's available balance.
+pub fn handler(ctx: Context<ReleaseHold>, hold_id: [u8; 32]) -> Result<()> {
+    let hold = &ctx.accounts.hold;
+    let clock = Clock::get()?;
+    // A hold emptied by unfreeze or settle can be closed right away
+    require!(hold.amount == 0 || clock.unix_timestamp >= hold.release_at, ErrorCode::HoldNotReleasable);
+
+    let amount = hold.amount;
+    let user_token_account = &mut ctx.accounts.user_token_account;
+    user_token_account.held = user_token_account.held.checked_sub(amount).ok_or(ErrorCode::InsufficientFrozen)?;
+    user_token_account.frozen = user_token_account.frozen.checked_sub(amount).ok_or(ErrorCode::InsufficientFrozen)?;
+    user_token_account.available = user_token_account.available.checked_add(amount).unwrap();
+
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    mint_totals.sub_frozen(amount)?;
+    mint_totals.add_available(amount)?;
+
+    emit!(HoldReleasedEvent {
+        hold_id,
+        account: hold.account,
+        token: hold.mint,
+        amount,
+        user: ctx.accounts.user.key(),
+    });
+    Ok(())
+}
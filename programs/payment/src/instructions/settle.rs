@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::utils::{verify_ed25519_instruction, transfer_sol, transfer_token, get_ata_owner_from_unchecked_account};
+use crate::utils::{verify_signature, verify_quorum, consume_sn, draw_hold, transfer_sol, transfer_token, sync_if_wrapped, get_ata_owner_from_unchecked_account};
 use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
 use crate::events::SettleEvent;
 use crate::Settlement;
-use crate::state::{SettlementData};
+use crate::state::{PaymentState, SettlementData};
 use solana_program::pubkey::Pubkey;
 
 pub fn handler(
@@ -13,34 +14,50 @@ pub fn handler(
     expired_at: i64,
     signature: [u8; 64],
 ) -> Result<()> {
-    // Check if the record already exists
-    require!(ctx.accounts.record.executed == false, ErrorCode::AlreadyExecuted);
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_SETTLE), ErrorCode::Paused);
     // Check if the request is expired
     let clock = Clock::get()?;
     require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
 
+    require!(deal.is_valid(), ErrorCode::InvalidParameter);
+    // Each ledger may only be loaded once, or the last write would win
+    let fee_to_account = ctx.accounts.payment_state.fee_to_account;
     require!(
-        deal.available + deal.frozen == deal.amount + deal.fee && 
-        deal.amount + deal.fee > 0 && 
-        deal.paid >= deal.frozen + deal.excess_fee && 
-        deal.frozen >= deal.excess_fee, ErrorCode::InvalidParameter
+        deal.from != deal.to && deal.from != fee_to_account && deal.to != fee_to_account,
+        ErrorCode::InvalidParameter
     );
 
-    let message = [
+    let message = build_message(Operation::Settle, ReplayMode::of(&ctx.accounts.record), &[
         &sn[..], &deal.to_bytes()[..], &expired_at.to_le_bytes(),
-        &ctx.accounts.user.key().to_bytes()[..], 
+        &ctx.accounts.user.key().to_bytes()[..],
         &ctx.accounts.mint.key().to_bytes()[..],
-        &ctx.accounts.out.key().to_bytes()[..], 
+        &ctx.accounts.out.key().to_bytes()[..],
         &ctx.accounts.fee_user.key().to_bytes()[..],
-    ].concat();
+    ]);
 
-    verify_ed25519_instruction(
+    verify_signature(
         &ctx.accounts.instruction_sysvar,
-        ctx.accounts.payment_state.signer.as_ref(),
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
         &message,
         &signature,
     )?;
 
+    // Paying out to an external account is high-risk and needs the quorum of signers
+    if ctx.accounts.out.key() != Pubkey::default() && deal.amount > 0 {
+        verify_quorum(&ctx.accounts.instruction_sysvar, &ctx.accounts.payment_state, &message)?;
+    }
+
+    // An order's hold can only pay out to the beneficiary it was reserved for, so the
+    // held part must be credited to the beneficiary's ledger rather than paid out to `out`
+    if let Some(hold) = &ctx.accounts.hold {
+        require!(
+            hold.beneficiary == [0u8; 32] || (hold.beneficiary == deal.to && ctx.accounts.out.key() == Pubkey::default()),
+            ErrorCode::InvalidParameter
+        );
+    }
+
     if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
         require!(ctx.accounts.fee_user.key() == ctx.accounts.payment_state.fee_to, ErrorCode::InvalidFeeUser);
     } else {
@@ -48,10 +65,18 @@ pub fn handler(
         require!(fee_user_owner == ctx.accounts.payment_state.fee_to, ErrorCode::InvalidFeeUser); 
     }
 
-    // Mark the record as executed
-    ctx.accounts.record.executed = true;
+    // Mark the transaction as executed
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.from_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
 
     let from_account = &mut ctx.accounts.from_token_account;
+    let mint_totals = &mut ctx.accounts.mint_totals;
     
     if from_account.mint == Pubkey::default() {
         from_account.mint = ctx.accounts.mint.key();
@@ -60,23 +85,32 @@ pub fn handler(
     if deal.available > 0 {
         require!(from_account.available >= deal.available, ErrorCode::InsufficientAvailable);
         from_account.available = from_account.available.checked_sub(deal.available).unwrap();
+        mint_totals.sub_available(deal.available)?;
     }
     
     if deal.frozen > 0 {
-        require!(from_account.frozen >= deal.frozen, ErrorCode::InsufficientFrozen);
+        // paid covers deal.frozen plus any excess being unfrozen, all of it drawn from the deal's hold
+        draw_hold(&mut ctx.accounts.hold, from_account, &deal.hold_id, &deal.from, &ctx.accounts.mint.key(), deal.paid)?;
+        require!(from_account.frozen >= deal.paid, ErrorCode::InsufficientFrozen);
         from_account.frozen = from_account.frozen.checked_sub(deal.frozen).unwrap();
+        mint_totals.sub_frozen(deal.frozen)?;
         // if paid greater than 'frozen', it indicates that the excess amount needs to be unfrozen
         if deal.paid > deal.frozen {
             let excess_amount = deal.paid - deal.frozen;
             from_account.frozen -= excess_amount;
             from_account.available = from_account.available + excess_amount - deal.excess_fee; 
+            mint_totals.sub_frozen(excess_amount)?;
+            mint_totals.add_available(excess_amount - deal.excess_fee)?;
         }
     }
 
     // Settlement tokens
     let total_fee = deal.fee + deal.excess_fee;
+    let mut received = deal.amount;
     // Check if the 'out' account is not a zero address
     if ctx.accounts.out.key() != Pubkey::default() {
+        let paid_out = deal.amount.checked_add(total_fee).ok_or(ErrorCode::InvalidAmount)?;
+        ctx.accounts.mint_config.sub_deposits(paid_out)?;
         if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
             if deal.amount > 0 {
                 transfer_sol(
@@ -86,6 +120,7 @@ pub fn handler(
                     ctx.bumps.program_token,
                     deal.amount,
                 )?;
+                sync_if_wrapped(&ctx.accounts.out, &ctx.accounts.token_program)?;
             }
             if total_fee > 0 {
                 transfer_sol(
@@ -98,9 +133,10 @@ pub fn handler(
             }
         } else {
             if deal.amount > 0 {
-                transfer_token(
+                received = transfer_token(
                     &ctx.accounts.program_token,
                     &ctx.accounts.out,
+                    &ctx.accounts.mint,
                     &ctx.accounts.token_program,
                     &ctx.accounts.payment_state,
                     ctx.bumps.payment_state,
@@ -111,6 +147,7 @@ pub fn handler(
                 transfer_token(
                     &ctx.accounts.program_token,
                     &ctx.accounts.fee_user,
+                    &ctx.accounts.mint,
                     &ctx.accounts.token_program,
                     &ctx.accounts.payment_state,
                     ctx.bumps.payment_state,
@@ -123,11 +160,16 @@ pub fn handler(
         if deal.amount > 0 {
             let to_account = &mut ctx.accounts.to_token_account;
             to_account.available += deal.amount;
+            ctx.accounts.mint_config.check_balance(to_account)?;
         }
         if total_fee > 0 {
             let fee_account = &mut ctx.accounts.fee_token_account;
             fee_account.available += total_fee;
         }
+
+        let mint_totals = &mut ctx.accounts.mint_totals;
+        mint_totals.add_available(deal.amount)?;
+        mint_totals.add_fees(total_fee)?;
     }
 
     // Emit transfer event
@@ -144,6 +186,8 @@ pub fn handler(
         fee: deal.fee,
         paid: deal.paid,
         excess_fee: deal.excess_fee,
+        hold_id: deal.hold_id,
+        received,
         user: ctx.accounts.user.key(),
     });
 
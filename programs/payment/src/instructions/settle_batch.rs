@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use crate::utils::{verify_signature, consume_sn, load_user_token_account};
+use crate::errors::ErrorCode;
+use crate::message::{build_message, hash_batch, Operation, ReplayMode};
+use crate::events::BatchSettleEvent;
+use crate::SettleBatch;
+use crate::state::{PaymentState, SettlementData, UserTokenAccount};
+
+pub const MAX_BATCH_DEALS: usize = 64;
+
+// Nets many ledger-only deals of one mint under a single signature. remaining_accounts
+// holds the user-token account of every distinct `from`/`to`, in order of first
+// appearance in the batch, so a deal can spend what an earlier one credited.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>,
+    sn: [u8; 32],
+    deals: Vec<SettlementData>,
+    expired_at: i64,
+    signature: [u8; 64],
+) -> Result<()> {
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_SETTLE), ErrorCode::Paused);
+    // Check if the request is expired
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
+
+    require!(!deals.is_empty() && deals.len() <= MAX_BATCH_DEALS, ErrorCode::InvalidBatch);
+    let fee_to_account = ctx.accounts.payment_state.fee_to_account;
+    let mut accounts: Vec<[u8; 32]> = Vec::new();
+    for deal in &deals {
+        // Holds are not supported in batches, only frozen funds outside any hold are used
+        require!(deal.is_valid() && deal.hold_id == [0u8; 32], ErrorCode::InvalidParameter);
+        require!(deal.from != deal.to, ErrorCode::InvalidBatch);
+        for account in [deal.from, deal.to] {
+            require!(account != fee_to_account, ErrorCode::InvalidBatch);
+            if !accounts.contains(&account) {
+                accounts.push(account);
+            }
+        }
+    }
+    require!(ctx.remaining_accounts.len() == accounts.len(), ErrorCode::InvalidBatch);
+
+    let batch_hash = hash_batch(deals.iter().map(SettlementData::to_bytes));
+    let message = build_message(Operation::SettleBatch, ReplayMode::of(&ctx.accounts.record), &[
+        &sn[..], &(deals.len() as u16).to_le_bytes(), &batch_hash[..], &expired_at.to_le_bytes(),
+        &ctx.accounts.user.key().to_bytes()[..],
+        &ctx.accounts.mint.key().to_bytes()[..],
+    ]);
+    verify_signature(
+        &ctx.accounts.instruction_sysvar,
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
+        &message,
+        &signature,
+    )?;
+
+    // A batch always needs its record, there is no single account to keep a nonce on
+    require!(ctx.accounts.record.is_some(), ErrorCode::MissingRecord);
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.fee_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
+
+    let mint = ctx.accounts.mint.key();
+    let mut ledgers: Vec<Account<'info, UserTokenAccount>> = Vec::with_capacity(accounts.len());
+    for (account, info) in accounts.iter().zip(ctx.remaining_accounts.iter()) {
+        ledgers.push(load_user_token_account(
+            info,
+            account,
+            &mint,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?);
+    }
+    let position = |account: &[u8; 32]| accounts.iter().position(|a| a == account).unwrap();
+
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    let mut total_fee: u64 = 0;
+    for (index, deal) in deals.iter().enumerate() {
+        let from_account = &mut ledgers[position(&deal.from)];
+        if deal.available > 0 {
+            require!(from_account.available >= deal.available, ErrorCode::InsufficientAvailable);
+            from_account.available -= deal.available;
+            mint_totals.sub_available(deal.available)?;
+        }
+        if deal.frozen > 0 {
+            // paid covers deal.frozen plus any excess being unfrozen
+            require!(from_account.unheld_frozen() >= deal.paid, ErrorCode::InsufficientFrozen);
+            from_account.frozen -= deal.frozen;
+            mint_totals.sub_frozen(deal.frozen)?;
+            // if paid greater than 'frozen', it indicates that the excess amount needs to be unfrozen
+            if deal.paid > deal.frozen {
+                let excess_amount = deal.paid - deal.frozen;
+                from_account.frozen -= excess_amount;
+                from_account.available = from_account.available + excess_amount - deal.excess_fee;
+                mint_totals.sub_frozen(excess_amount)?;
+                mint_totals.add_available(excess_amount - deal.excess_fee)?;
+            }
+        }
+
+        if deal.amount > 0 {
+            let to_account = &mut ledgers[position(&deal.to)];
+            to_account.available = to_account.available.checked_add(deal.amount).unwrap();
+            ctx.accounts.mint_config.check_balance(to_account)?;
+            mint_totals.add_available(deal.amount)?;
+        }
+
+        let fee = deal.fee + deal.excess_fee;
+        total_fee = total_fee.checked_add(fee).unwrap();
+        mint_totals.add_fees(fee)?;
+
+        emit!(BatchSettleEvent {
+            sn,
+            index: index as u16,
+            token: mint,
+            from: deal.from,
+            to: deal.to,
+            available: deal.available,
+            frozen: deal.frozen,
+            amount: deal.amount,
+            fee: deal.fee,
+            paid: deal.paid,
+            excess_fee: deal.excess_fee,
+            user: ctx.accounts.user.key(),
+        });
+    }
+
+    let fee_account = &mut ctx.accounts.fee_token_account;
+    fee_account.available = fee_account.available.checked_add(total_fee).unwrap();
+
+    for ledger in &ledgers {
+        ledger.exit(&crate::ID)?;
+    }
+    Ok(())
+}
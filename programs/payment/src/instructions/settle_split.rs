@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use crate::utils::{verify_signature, verify_quorum, consume_sn, draw_hold, load_user_token_account, transfer_sol, transfer_token, sync_if_wrapped};
+use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::events::SettleSplitEvent;
+use crate::SettleSplit;
+use crate::state::{PaymentState, SplitSettlementData};
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleSplit<'info>>,
+    sn: [u8; 32],
+    deal: SplitSettlementData,
+    expired_at: i64,
+    signature: [u8; 64],
+) -> Result<()> {
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_SETTLE), ErrorCode::Paused);
+    // Check if the request is expired
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
+
+    let payees = &deal.payees;
+    require!(!payees.is_empty() && payees.len() <= SplitSettlementData::MAX_PAYEES, ErrorCode::InvalidPayees);
+    require!(ctx.remaining_accounts.len() == payees.len(), ErrorCode::InvalidPayees);
+    let mut total = deal.fee;
+    for (i, payee) in payees.iter().enumerate() {
+        require!(payee.amount > 0, ErrorCode::InvalidPayees);
+        total = total.checked_add(payee.amount).ok_or(ErrorCode::InvalidAmount)?;
+        // Each ledger may only be loaded once, or the last write would win
+        if payee.out == Pubkey::default() {
+            require!(
+                payee.account != deal.from &&
+                payee.account != ctx.accounts.payment_state.fee_to_account &&
+                !payees[..i].iter().any(|other| other.out == Pubkey::default() && other.account == payee.account),
+                ErrorCode::InvalidPayees
+            );
+        }
+    }
+    require!(deal.available.checked_add(deal.frozen) == Some(total), ErrorCode::InvalidParameter);
+    require!(deal.frozen > 0 || deal.hold_id == [0u8; 32], ErrorCode::InvalidParameter);
+
+    let message = build_message(Operation::SettleSplit, ReplayMode::of(&ctx.accounts.record), &[
+        &sn[..], &deal.to_bytes()[..], &expired_at.to_le_bytes(),
+        &ctx.accounts.user.key().to_bytes()[..],
+        &ctx.accounts.mint.key().to_bytes()[..],
+    ]);
+    verify_signature(
+        &ctx.accounts.instruction_sysvar,
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
+        &message,
+        &signature,
+    )?;
+
+    // Paying out to an external account is high-risk and needs the quorum of signers
+    if payees.iter().any(|payee| payee.out != Pubkey::default()) {
+        verify_quorum(&ctx.accounts.instruction_sysvar, &ctx.accounts.payment_state, &message)?;
+    }
+
+    // An order's hold can only pay out to the beneficiary it was reserved for, so the
+    // frozen part must land on the beneficiary's ledger (ledgers are unique per deal)
+    if let Some(hold) = &ctx.accounts.hold {
+        require!(
+            hold.beneficiary == [0u8; 32] || payees.iter().any(|payee| {
+                payee.out == Pubkey::default() && payee.account == hold.beneficiary && payee.amount >= deal.frozen
+            }),
+            ErrorCode::InvalidParameter
+        );
+    }
+
+    // Mark the transaction as executed
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.from_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
+
+    let mint = ctx.accounts.mint.key();
+    let from_account = &mut ctx.accounts.from_token_account;
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    require!(from_account.available >= deal.available, ErrorCode::InsufficientAvailable);
+    from_account.available -= deal.available;
+    mint_totals.sub_available(deal.available)?;
+    if deal.frozen > 0 {
+        draw_hold(&mut ctx.accounts.hold, from_account, &deal.hold_id, &deal.from, &mint, deal.frozen)?;
+        require!(from_account.frozen >= deal.frozen, ErrorCode::InsufficientFrozen);
+        from_account.frozen -= deal.frozen;
+        mint_totals.sub_frozen(deal.frozen)?;
+    }
+
+    if deal.fee > 0 {
+        let fee_account = &mut ctx.accounts.fee_token_account;
+        fee_account.available = fee_account.available.checked_add(deal.fee).unwrap();
+        mint_totals.add_fees(deal.fee)?;
+    }
+
+    // Pay every leg, in the order of remaining_accounts
+    let is_native = mint == anchor_spl::token::spl_token::native_mint::id();
+    let mut received = Vec::with_capacity(payees.len());
+    for (payee, info) in payees.iter().zip(ctx.remaining_accounts.iter()) {
+        if payee.out == Pubkey::default() {
+            let mut to_account = load_user_token_account(
+                info,
+                &payee.account,
+                &mint,
+                &ctx.accounts.user.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+            to_account.available = to_account.available.checked_add(payee.amount).unwrap();
+            ctx.accounts.mint_config.check_balance(&to_account)?;
+            to_account.exit(&crate::ID)?;
+            mint_totals.add_available(payee.amount)?;
+            received.push(payee.amount);
+            continue;
+        }
+
+        require!(info.key() == payee.out, ErrorCode::InvalidPayees);
+        ctx.accounts.mint_config.sub_deposits(payee.amount)?;
+        if is_native {
+            transfer_sol(
+                &ctx.accounts.program_token,
+                info,
+                &ctx.accounts.system_program,
+                ctx.bumps.program_token,
+                payee.amount,
+            )?;
+            sync_if_wrapped(info, &ctx.accounts.token_program)?;
+            received.push(payee.amount);
+        } else {
+            received.push(transfer_token(
+                &ctx.accounts.program_token,
+                info,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_program,
+                &ctx.accounts.payment_state,
+                ctx.bumps.payment_state,
+                payee.amount,
+            )?);
+        }
+    }
+
+    emit!(SettleSplitEvent {
+        sn,
+        token: mint,
+        from: deal.from,
+        available: deal.available,
+        frozen: deal.frozen,
+        fee: deal.fee,
+        hold_id: deal.hold_id,
+        payees: deal.payees,
+        received,
+        user: ctx.accounts.user.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::utils::{vault_assets, transfer_sol, transfer_token};
+use crate::errors::ErrorCode;
+use crate::events::SurplusSweptEvent;
+use crate::SweepSurplus;
+
+pub fn handler(ctx: Context<SweepSurplus>, amount: u64) -> Result<()> {
+    require!(ctx.accounts.owner.key() == ctx.accounts.payment_state.owner, ErrorCode::Unauthorized);
+    require!(amount > 0, ErrorCode::ZeroAmount);
+
+    // Only the part of the vault not owed to any ledger account can leave. Unseeded totals
+    // understate what is owed, so SweepSurplus requires seeded ones.
+    let assets = vault_assets(&ctx.accounts.mint, &ctx.accounts.program_token)?;
+    let surplus = assets.saturating_sub(ctx.accounts.mint_totals.liabilities());
+    require!(amount <= surplus, ErrorCode::InsufficientSurplus);
+
+    if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
+        transfer_sol(
+            &ctx.accounts.program_token,
+            &ctx.accounts.to,
+            &ctx.accounts.system_program,
+            ctx.bumps.program_token,
+            amount,
+        )?;
+    } else {
+        transfer_token(
+            &ctx.accounts.program_token,
+            &ctx.accounts.to,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+            &ctx.accounts.payment_state,
+            ctx.bumps.payment_state,
+            amount,
+        )?;
+    }
+
+    emit!(SurplusSweptEvent {
+        token: ctx.accounts.mint.key(),
+        to: ctx.accounts.to.key(),
+        amount,
+        surplus,
+        user: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::utils::{verify_ed25519_instruction, transfer_sol, transfer_token, get_ata_owner_from_unchecked_account};
+use crate::utils::{verify_signature, verify_quorum, consume_sn, transfer_sol, transfer_token, get_ata_owner_from_unchecked_account};
 use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::state::PaymentState;
 use crate::events::TransferEvent;
 use crate::Transfer;
 
@@ -14,28 +16,37 @@ pub fn handler(
     expired_at: i64,
     signature: [u8; 64],
 ) -> Result<()> {
-    // Check if the record already exists
-    require!(ctx.accounts.record.executed == false, ErrorCode::AlreadyExecuted);
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_TRANSFER), ErrorCode::Paused);
     // Check if the request is expired
     let clock = Clock::get()?;
     require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
 
     require!(amount > 0 && amount > fee, ErrorCode::InvalidParameter);
+    // Each ledger may only be loaded once, or the last write would win
+    let fee_to_account = ctx.accounts.payment_state.fee_to_account;
+    require!(from != to && from != fee_to_account && to != fee_to_account, ErrorCode::InvalidParameter);
 
-    let message = [
+    let message = build_message(Operation::Transfer, ReplayMode::of(&ctx.accounts.record), &[
         &sn[..], &from[..], &to[..], &amount.to_le_bytes(), &fee.to_le_bytes(), &expired_at.to_le_bytes(),
         &ctx.accounts.user.key().to_bytes()[..],
         &ctx.accounts.mint.key().to_bytes()[..],
-        &ctx.accounts.out.key().to_bytes()[..], 
-        &ctx.accounts.fee_user.key().to_bytes()[..], 
-    ].concat();
-    verify_ed25519_instruction(
+        &ctx.accounts.out.key().to_bytes()[..],
+        &ctx.accounts.fee_user.key().to_bytes()[..],
+    ]);
+    verify_signature(
         &ctx.accounts.instruction_sysvar,
-        ctx.accounts.payment_state.signer.as_ref(),
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
         &message,
         &signature,
     )?;
 
+    // Paying out to an external account is high-risk and needs the quorum of signers
+    if ctx.accounts.out.key() != Pubkey::default() {
+        verify_quorum(&ctx.accounts.instruction_sysvar, &ctx.accounts.payment_state, &message)?;
+    }
+
     // msg!("payment_state.fee_to: {}", ctx.accounts.payment_state.fee_to.to_string());
     if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
         // msg!("fee_user wallet: {}", ctx.accounts.fee_user.key().to_string());
@@ -46,17 +57,27 @@ pub fn handler(
         require!(fee_user_owner == ctx.accounts.payment_state.fee_to, ErrorCode::InvalidFeeUser); // Added usage of fee_user_token_account
     }
 
-    // Mark the record as executed
-    ctx.accounts.record.executed = true;
+    // Mark the transaction as executed
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.from_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
 
     let from_token_account = &mut ctx.accounts.from_token_account;
     require!(from_token_account.available >= amount, ErrorCode::InsufficientAvailable);
 
     from_token_account.available = from_token_account.available.checked_sub(amount).unwrap();
+    ctx.accounts.mint_totals.sub_available(amount)?;
 
     // Check if the 'out' account is not a zero address
     let to_amount = amount - fee;
+    let mut received = to_amount;
     if ctx.accounts.out.key() != Pubkey::default() {
+        ctx.accounts.mint_config.sub_deposits(amount)?;
         if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
             if amount > 0 {
                 transfer_sol(
@@ -78,9 +99,10 @@ pub fn handler(
             }
         } else {
             if amount > 0 {
-                transfer_token(
+                received = transfer_token(
                     &ctx.accounts.program_token,
                     &ctx.accounts.out,
+                    &ctx.accounts.mint,
                     &ctx.accounts.token_program,
                     &ctx.accounts.payment_state,
                     ctx.bumps.payment_state,
@@ -91,6 +113,7 @@ pub fn handler(
                 transfer_token(
                     &ctx.accounts.program_token,
                     &ctx.accounts.fee_user,
+                    &ctx.accounts.mint,
                     &ctx.accounts.token_program,
                     &ctx.accounts.payment_state,
                     ctx.bumps.payment_state,
@@ -102,11 +125,16 @@ pub fn handler(
         // If 'out' is a zero address, only update account funds
         let to_token_account = &mut ctx.accounts.to_token_account;
         to_token_account.available += to_amount;
+        ctx.accounts.mint_config.check_balance(to_token_account)?;
 
         if fee > 0 {
             let fee_token_account = &mut ctx.accounts.fee_token_account;
             fee_token_account.available += fee;
         }
+
+        let mint_totals = &mut ctx.accounts.mint_totals;
+        mint_totals.add_available(to_amount)?;
+        mint_totals.add_fees(fee)?;
     }
 
     // Emit Transfer event
@@ -119,6 +147,7 @@ pub fn handler(
         fee_user: ctx.accounts.fee_user.key(),
         amount,
         fee,
+        received,
         user: ctx.accounts.user.key(),
     });
     Ok(())
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::utils::{verify_signature, consume_sn, load_user_token_account};
+use crate::errors::ErrorCode;
+use crate::message::{build_message, hash_batch, Operation, ReplayMode};
+use crate::events::TransferManyEvent;
+use crate::TransferMany;
+use crate::state::{PaymentState, TransferRecipient};
+
+pub const MAX_TRANSFER_RECIPIENTS: usize = 64;
+
+// Debits one `from` ledger and credits every recipient's ledger under a single signature.
+// remaining_accounts holds the user-token account of each recipient, in the same order;
+// missing ones are created, paid for by `user`.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TransferMany<'info>>,
+    sn: [u8; 32],
+    from: [u8; 32],
+    recipients: Vec<TransferRecipient>,
+    fee: u64,
+    expired_at: i64,
+    signature: [u8; 64],
+) -> Result<()> {
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_TRANSFER), ErrorCode::Paused);
+    // Check if the request is expired
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
+
+    require!(!recipients.is_empty() && recipients.len() <= MAX_TRANSFER_RECIPIENTS, ErrorCode::InvalidRecipients);
+    let fee_to_account = ctx.accounts.payment_state.fee_to_account;
+    let mut total: u64 = 0;
+    for (index, recipient) in recipients.iter().enumerate() {
+        require!(recipient.amount > 0, ErrorCode::InvalidParameter);
+        require!(
+            recipient.to != from
+                && recipient.to != fee_to_account
+                && !recipients[..index].iter().any(|r| r.to == recipient.to),
+            ErrorCode::InvalidRecipients
+        );
+        total = total.checked_add(recipient.amount).ok_or(ErrorCode::InvalidParameter)?;
+    }
+    require!(ctx.remaining_accounts.len() == recipients.len(), ErrorCode::InvalidRecipients);
+    let debit = total.checked_add(fee).ok_or(ErrorCode::InvalidParameter)?;
+
+    let recipients_hash = hash_batch(recipients.iter().map(TransferRecipient::to_bytes));
+    let message = build_message(Operation::TransferMany, ReplayMode::of(&ctx.accounts.record), &[
+        &sn[..], &from[..], &(recipients.len() as u16).to_le_bytes(), &recipients_hash[..],
+        &fee.to_le_bytes(), &expired_at.to_le_bytes(),
+        &ctx.accounts.user.key().to_bytes()[..],
+        &ctx.accounts.mint.key().to_bytes()[..],
+    ]);
+    verify_signature(
+        &ctx.accounts.instruction_sysvar,
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
+        &message,
+        &signature,
+    )?;
+
+    // Mark the transaction as executed
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.from_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
+
+    let from_token_account = &mut ctx.accounts.from_token_account;
+    require!(from_token_account.available >= debit, ErrorCode::InsufficientAvailable);
+    from_token_account.available -= debit;
+
+    let mint = ctx.accounts.mint.key();
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    mint_totals.sub_available(debit)?;
+    for (recipient, info) in recipients.iter().zip(ctx.remaining_accounts.iter()) {
+        let mut to_account = load_user_token_account(
+            info,
+            &recipient.to,
+            &mint,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        to_account.available = to_account.available.checked_add(recipient.amount).unwrap();
+        ctx.accounts.mint_config.check_balance(&to_account)?;
+        mint_totals.add_available(recipient.amount)?;
+        to_account.exit(&crate::ID)?;
+    }
+
+    if fee > 0 {
+        let fee_token_account = &mut ctx.accounts.fee_token_account;
+        fee_token_account.available = fee_token_account.available.checked_add(fee).unwrap();
+        mint_totals.add_fees(fee)?;
+    }
+
+    emit!(TransferManyEvent {
+        sn,
+        token: mint,
+        from,
+        recipients,
+        total,
+        fee,
+        user: ctx.accounts.user.key(),
+    });
+    Ok(())
+}
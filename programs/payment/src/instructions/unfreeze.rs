@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::utils::verify_ed25519_instruction;
+use crate::utils::{verify_signature, consume_sn, draw_hold};
 use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::state::PaymentState;
 use crate::events::UnfreezeEvent;
 use crate::Unfreeze;
 
@@ -10,34 +12,44 @@ pub fn handler(
     account: [u8; 32],
     amount: u64,
     fee: u64,
+    hold_id: [u8; 32],
     expired_at: i64,
     signature: [u8; 64],
 ) -> Result<()> {
     require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_UNFREEZE), ErrorCode::Paused);
     require!(amount > 0, ErrorCode::ZeroAmount);
     require!(amount > fee, ErrorCode::FeeOverrun);
 
     let clock = Clock::get()?;
     require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
 
-    let message = [
-        &sn[..], &account[..], &amount.to_le_bytes(), &fee.to_le_bytes(), &expired_at.to_le_bytes(),
+    let message = build_message(Operation::Unfreeze, ReplayMode::of(&ctx.accounts.record), &[
+        &sn[..], &account[..], &amount.to_le_bytes(), &fee.to_le_bytes(), &hold_id[..], &expired_at.to_le_bytes(),
         &ctx.accounts.user.key().to_bytes()[..],
         &ctx.accounts.mint.key().to_bytes()[..],
-    ].concat();
-    verify_ed25519_instruction(
+    ]);
+    verify_signature(
         &ctx.accounts.instruction_sysvar,
-        ctx.accounts.payment_state.signer.as_ref(),
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
         &message,
         &signature,
     )?;
     
     // Check if the transaction has already been executed
-    require!(!ctx.accounts.record.executed, ErrorCode::AlreadyExecuted);
-    ctx.accounts.record.executed = true;
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.user_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
 
-    // Check if the user has enough available balance
+    // Check if the user has enough frozen balance in the hold, or outside any hold
     let user_token_account = &mut ctx.accounts.user_token_account;
+    draw_hold(&mut ctx.accounts.hold, user_token_account, &hold_id, &account, &ctx.accounts.mint.key(), amount)?;
     require!(user_token_account.frozen >= amount, ErrorCode::InsufficientFrozen);
 
     // Update balances
@@ -49,6 +61,11 @@ pub fn handler(
         fee_token_account.available += fee;
     }
 
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    mint_totals.sub_frozen(amount)?;
+    mint_totals.add_available(amount - fee)?;
+    mint_totals.add_fees(fee)?;
+
     // Emit the unfreeze event
     emit!(UnfreezeEvent {
         sn,
@@ -56,6 +73,7 @@ pub fn handler(
         token: ctx.accounts.mint.key(),
         amount,
         fee,
+        hold_id,
         user: ctx.accounts.user.key(),
     });
     Ok(())
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::utils::{verify_ed25519_instruction, transfer_sol, transfer_token};
+use crate::utils::{verify_signature, verify_quorum, consume_sn, transfer_sol, transfer_token, sync_if_wrapped};
 use crate::errors::ErrorCode;
+use crate::message::{build_message, Operation, ReplayMode};
+use crate::state::PaymentState;
 use crate::events::WithdrawEvent;
 use crate::Withdraw;
 
@@ -13,31 +15,42 @@ pub fn handler(
     expired_at: i64,
     signature: [u8; 64],
 ) -> Result<()> {
-    // Check if the record already exists
-    require!(ctx.accounts.record.executed == false, ErrorCode::AlreadyExecuted);
+    require!(ctx.accounts.payment_state.enabled, ErrorCode::Disabled);
+    require!(!ctx.accounts.payment_state.is_paused(PaymentState::PAUSE_WITHDRAW), ErrorCode::Paused);
     // Check if the request is expired
     let clock = Clock::get()?;
     require!(clock.unix_timestamp < expired_at, ErrorCode::Expired);
 
-    let message = [
+    let message = build_message(Operation::Withdraw, ReplayMode::of(&ctx.accounts.record), &[
         &sn[..], &from[..], &available.to_le_bytes(), &frozen.to_le_bytes(), &expired_at.to_le_bytes(),
         &ctx.accounts.user.key().to_bytes()[..],
         &ctx.accounts.mint.key().to_bytes()[..],
         &ctx.accounts.to.key().to_bytes()[..],
-    ].concat();
-    verify_ed25519_instruction(
+    ]);
+    verify_signature(
         &ctx.accounts.instruction_sysvar,
-        ctx.accounts.payment_state.signer.as_ref(),
+        &ctx.accounts.payment_state,
+        clock.unix_timestamp,
         &message,
         &signature,
     )?;
 
-    // Mark the record as executed
-    ctx.accounts.record.executed = true;
+    // Withdrawals are high-risk and need the quorum of signers when one is configured
+    verify_quorum(&ctx.accounts.instruction_sysvar, &ctx.accounts.payment_state, &message)?;
+
+    // Mark the transaction as executed
+    consume_sn(
+        &ctx.accounts.payment_state,
+        &mut ctx.accounts.record,
+        &mut ctx.accounts.user_token_account,
+        &sn,
+        expired_at,
+        ctx.accounts.user.key(),
+    )?;
 
     let user_token_account = &mut ctx.accounts.user_token_account;
     require!(user_token_account.available >= available, ErrorCode::InsufficientAvailable);
-    require!(user_token_account.frozen >= frozen, ErrorCode::InsufficientFrozen);
+    require!(user_token_account.unheld_frozen() >= frozen, ErrorCode::InsufficientFrozen);
 
     user_token_account.available = user_token_account.available.checked_sub(available).unwrap();
     user_token_account.frozen = user_token_account.frozen.checked_sub(frozen).unwrap();
@@ -46,10 +59,15 @@ pub fn handler(
         user_token_account.mint = ctx.accounts.mint.key();
     }
 
+    let mint_totals = &mut ctx.accounts.mint_totals;
+    mint_totals.sub_available(available)?;
+    mint_totals.sub_frozen(frozen)?;
+
     // Settlement tokens
     let total_amount = available.checked_add(frozen).unwrap();
+    ctx.accounts.mint_config.sub_deposits(total_amount)?;
 
-    if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
+    let received = if ctx.accounts.mint.key() == anchor_spl::token::spl_token::native_mint::id() {
         transfer_sol(
             &ctx.accounts.program_token,
             &ctx.accounts.to,
@@ -57,16 +75,19 @@ pub fn handler(
             ctx.bumps.program_token,
             total_amount,
         )?;
+        sync_if_wrapped(&ctx.accounts.to, &ctx.accounts.token_program)?;
+        total_amount
     } else {
         transfer_token(
             &ctx.accounts.program_token,
             &ctx.accounts.to,
+            &ctx.accounts.mint,
             &ctx.accounts.token_program,
             &ctx.accounts.payment_state,
             ctx.bumps.payment_state,
             total_amount,
-        )?;
-    }
+        )?
+    };
 
     // Emit withdraw event
     emit!(WithdrawEvent {
@@ -76,6 +97,7 @@ pub fn handler(
         to: ctx.accounts.to.key(),
         available,
         frozen,
+        received,
         user: ctx.accounts.user.key(),
     });
 
@@ -1,7 +1,9 @@
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token};
+use anchor_spl::token_interface::TokenInterface;
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{PaymentState, UserTokenAccount, TransactionRecord, SettlementData};
+use crate::state::{PaymentState, UserTokenAccount, TransactionRecord, SettlementData, SplitSettlementData, TransferRecipient, MintConfig, MintConfigParams, MintTotals, Hold};
 
 declare_id!("CVnY7DzpU5TcJa8DyW4Nsgv2DVGWKHxWca1grt3EqKWT");
 
@@ -9,9 +11,11 @@ pub mod state;
 pub mod errors;
 pub mod events;
 pub mod utils;
+pub mod message;
 pub mod instructions;
 
 use instructions::*;
+use errors::ErrorCode;
 
 #[program]
 pub mod payment {
@@ -25,18 +29,113 @@ pub mod payment {
         admin::initialize_program_token(ctx)
     }
 
+    pub fn seed_mint_totals(ctx: Context<SeedMintTotals>, total_available: u64, total_frozen: u64, total_fees: u64) -> Result<()> {
+        admin::seed_mint_totals(ctx, total_available, total_frozen, total_fees)
+    }
+
     pub fn change_owner(ctx: Context<ChangeOwner>) -> Result<()> {
         admin::change_owner(ctx)
     }
 
+    pub fn accept_ownership(ctx: Context<AcceptRole>) -> Result<()> {
+        admin::accept_ownership(ctx)
+    }
+
+    pub fn cancel_ownership(ctx: Context<CancelRole>) -> Result<()> {
+        admin::cancel_ownership(ctx)
+    }
+
     pub fn change_signer(ctx: Context<ChangeOwner>) -> Result<()> {
         admin::change_signer(ctx)
     }
 
+    pub fn accept_signer(ctx: Context<AcceptRole>) -> Result<()> {
+        admin::accept_signer(ctx)
+    }
+
+    pub fn cancel_signer(ctx: Context<CancelRole>) -> Result<()> {
+        admin::cancel_signer(ctx)
+    }
+
     pub fn change_fee_to(ctx: Context<ChangeOwner>) -> Result<()> {
         admin::change_fee_to(ctx)
     }
 
+    pub fn accept_fee_to(ctx: Context<AcceptRole>) -> Result<()> {
+        admin::accept_fee_to(ctx)
+    }
+
+    pub fn cancel_fee_to(ctx: Context<CancelRole>) -> Result<()> {
+        admin::cancel_fee_to(ctx)
+    }
+
+    pub fn set_enabled(ctx: Context<ChangeConfig>, enabled: bool) -> Result<()> {
+        admin::set_enabled(ctx, enabled)
+    }
+
+    pub fn set_paused(ctx: Context<ChangeConfig>, paused: u8) -> Result<()> {
+        admin::set_paused(ctx, paused)
+    }
+
+    pub fn set_nosn_enabled(ctx: Context<ChangeConfig>, enabled: bool) -> Result<()> {
+        admin::set_nosn_enabled(ctx, enabled)
+    }
+
+    pub fn set_record_retention(ctx: Context<ChangeConfig>, retention: i64) -> Result<()> {
+        admin::set_record_retention(ctx, retention)
+    }
+
+    pub fn change_fee_to_account<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChangeFeeToAccount<'info>>,
+        fee_to_account: [u8; 32],
+    ) -> Result<()> {
+        admin::change_fee_to_account(ctx, fee_to_account)
+    }
+
+    pub fn migrate_fee_account(ctx: Context<MigrateFeeAccount>) -> Result<()> {
+        admin::migrate_fee_account(ctx)
+    }
+
+    pub fn add_quorum_signer(ctx: Context<ChangeConfig>, signer: Pubkey) -> Result<()> {
+        admin::add_quorum_signer(ctx, signer)
+    }
+
+    pub fn remove_quorum_signer(ctx: Context<ChangeConfig>, signer: Pubkey) -> Result<()> {
+        admin::remove_quorum_signer(ctx, signer)
+    }
+
+    pub fn set_quorum_threshold(ctx: Context<ChangeConfig>, threshold: u8) -> Result<()> {
+        admin::set_quorum_threshold(ctx, threshold)
+    }
+
+    pub fn set_signer_overlap(ctx: Context<ChangeConfig>, overlap: i64) -> Result<()> {
+        admin::set_signer_overlap(ctx, overlap)
+    }
+
+    pub fn set_exit_delay(ctx: Context<ChangeConfig>, delay: i64) -> Result<()> {
+        admin::set_exit_delay(ctx, delay)
+    }
+
+    pub fn change_eth_signer(ctx: Context<ChangeConfig>, eth_signer: [u8; 20]) -> Result<()> {
+        admin::change_eth_signer(ctx, eth_signer)
+    }
+
+    pub fn accept_eth_signer(ctx: Context<AcceptEthSigner>, signature: [u8; 64]) -> Result<()> {
+        admin::accept_eth_signer(ctx, signature)
+    }
+
+    pub fn cancel_eth_signer(ctx: Context<ChangeConfig>) -> Result<()> {
+        admin::cancel_eth_signer(ctx)
+    }
+
+    pub fn set_signature_scheme(ctx: Context<ChangeConfig>, scheme: u8) -> Result<()> {
+        admin::set_signature_scheme(ctx, scheme)
+    }
+
+    pub fn set_mint_config(ctx: Context<SetMintConfig>, params: MintConfigParams) -> Result<()> {
+        admin::set_mint_config(ctx, params)
+    }
+
     pub fn deposit(
         ctx: Context<Deposit>,
         sn: [u8; 32],
@@ -66,10 +165,13 @@ pub mod payment {
         sn: [u8; 32],
         account: [u8; 32],
         amount: u64,
+        hold_id: [u8; 32],
+        beneficiary: [u8; 32],
+        release_at: i64,
         expired_at: i64,
         signature: [u8; 64],
     ) -> Result<()> {
-        freeze::handler(ctx, sn, account, amount, expired_at, signature)
+        freeze::handler(ctx, sn, account, amount, hold_id, beneficiary, release_at, expired_at, signature)
     }
 
     pub fn unfreeze(
@@ -78,10 +180,11 @@ pub mod payment {
         account: [u8; 32],
         amount: u64, // amount to unfreeze
         fee: u64,  // fee to deduct, from amount
+        hold_id: [u8; 32], // hold to unfreeze from, zero for funds frozen without a hold
         expired_at: i64,
         signature: [u8; 64],
     ) -> Result<()> {
-        unfreeze::handler(ctx, sn, account, amount, fee, expired_at, signature)
+        unfreeze::handler(ctx, sn, account, amount, fee, hold_id, expired_at, signature)
     }
 
     pub fn transfer(
@@ -106,6 +209,95 @@ pub mod payment {
     ) -> Result<()> {
         settle::handler(ctx, sn, deal, expired_at, signature)
     }
+
+    pub fn settle_split<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleSplit<'info>>,
+        sn: [u8; 32],
+        deal: SplitSettlementData,
+        expired_at: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        settle_split::handler(ctx, sn, deal, expired_at, signature)
+    }
+
+    pub fn settle_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>,
+        sn: [u8; 32],
+        deals: Vec<SettlementData>,
+        expired_at: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        settle_batch::handler(ctx, sn, deals, expired_at, signature)
+    }
+
+    pub fn transfer_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TransferMany<'info>>,
+        sn: [u8; 32],
+        from: [u8; 32],
+        recipients: Vec<TransferRecipient>,
+        fee: u64, // fee credited to the fee account, on top of the recipients' total
+        expired_at: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        transfer_many::handler(ctx, sn, from, recipients, fee, expired_at, signature)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>, amount: u64) -> Result<()> {
+        claim_fees::handler(ctx, amount)
+    }
+
+    pub fn bind_wallet(
+        ctx: Context<BindWallet>,
+        sn: [u8; 32],
+        account: [u8; 32],
+        wallet: Pubkey,
+        expired_at: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        exit::bind_wallet(ctx, sn, account, wallet, expired_at, signature)
+    }
+
+    pub fn request_exit(ctx: Context<RequestExit>, account: [u8; 32]) -> Result<()> {
+        exit::request_exit(ctx, account)
+    }
+
+    pub fn cancel_exit(
+        ctx: Context<CancelExit>,
+        sn: [u8; 32],
+        account: [u8; 32],
+        expired_at: i64,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        exit::cancel_exit(ctx, sn, account, expired_at, signature)
+    }
+
+    pub fn execute_exit(ctx: Context<ExecuteExit>, account: [u8; 32]) -> Result<()> {
+        exit::execute_exit(ctx, account)
+    }
+
+    pub fn release_hold(ctx: Context<ReleaseHold>, hold_id: [u8; 32]) -> Result<()> {
+        release_hold::handler(ctx, hold_id)
+    }
+
+    pub fn check_solvency(ctx: Context<CheckSolvency>) -> Result<()> {
+        check_solvency::handler(ctx)
+    }
+
+    pub fn sweep_surplus(ctx: Context<SweepSurplus>, amount: u64) -> Result<()> {
+        sweep_surplus::handler(ctx, amount)
+    }
+
+    pub fn close_records<'info>(ctx: Context<'_, '_, 'info, 'info, CloseRecords<'info>>) -> Result<()> {
+        close_records::handler(ctx)
+    }
+
+    pub fn migrate_payment_state(ctx: Context<MigratePaymentState>) -> Result<()> {
+        migrate::migrate_payment_state(ctx)
+    }
+
+    pub fn migrate_user_token_account(ctx: Context<MigrateUserTokenAccount>, account: [u8; 32]) -> Result<()> {
+        migrate::migrate_user_token_account(ctx, account)
+    }
 }
 
 #[derive(Accounts)]
@@ -136,7 +328,7 @@ pub struct InitializeProgramToken<'info> {
         payer = owner,
         seeds = [b"program-token", mint.key().as_ref()],
         bump,
-        space = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { 0 } else { 165 },
+        space = utils::program_token_space(&mint),
         owner = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { system_program.key() } else { token_program.key() }
     )]
     /// CHECK: This account is checked in the instruction
@@ -149,11 +341,44 @@ pub struct InitializeProgramToken<'info> {
         bump
     )]
     pub fee_token_account: Account<'info, UserTokenAccount>,
-    pub token_program: Program<'info, Token>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + MintTotals::LEN,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct SeedMintTotals<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: Only used as a seed
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + MintTotals::LEN,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct ChangeOwner<'info> {
     #[account(mut)]
@@ -163,6 +388,89 @@ pub struct ChangeOwner<'info> {
     pub new_owner: UncheckedAccount<'info>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptRole<'info> {
+    #[account(mut, seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    pub new_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptEthSigner<'info> {
+    #[account(mut, seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    /// CHECK: This account is used to verify the Secp256k1 instruction
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRole<'info> {
+    #[account(mut, seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    pub current_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeConfig<'info> {
+    #[account(mut, seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMintConfig<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + MintConfig::LEN,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeFeeToAccount<'info> {
+    #[account(mut, seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateFeeAccount<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"user-token", payment_state.previous_fee_to_account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub old_fee_token_account: Account<'info, UserTokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserTokenAccount::LEN,
+        seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, UserTokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(sn: [u8; 32], account: [u8; 32])]
 pub struct Deposit<'info> {
@@ -180,6 +488,20 @@ pub struct Deposit<'info> {
     pub user: Signer<'info>,
     /// CHECK: This account is checked in the instruction handler
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        constraint = mint_config.enabled @ ErrorCode::MintNotEnabled
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
     /// CHECK: This is the token account that we want to transfer to
     #[account(mut)]
     pub user_token: UncheckedAccount<'info>,
@@ -191,6 +513,14 @@ pub struct Deposit<'info> {
         owner = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { system_program.key() } else { token_program.key() }
     )]
     pub program_token: UncheckedAccount<'info>,
+    /// CHECK: Scratch wSOL account, created and closed within a native mint deposit
+    /// from a wSOL account. Not needed for other deposits.
+    #[account(
+        mut,
+        seeds = [b"native-unwrap", user.key().as_ref()],
+        bump
+    )]
+    pub native_unwrap: Option<UncheckedAccount<'info>>,
     #[account(
         init_if_needed,
         payer = user,
@@ -198,8 +528,8 @@ pub struct Deposit<'info> {
         seeds = [b"record", sn.as_ref()],
         bump
     )]
-    pub record: Account<'info, TransactionRecord>,
-    pub token_program: Program<'info, Token>,
+    pub record: Option<Account<'info, TransactionRecord>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     /// CHECK: This account is used to verify the Ed25519 instruction
@@ -224,6 +554,20 @@ pub struct Withdraw<'info> {
     pub user: Signer<'info>,
     /// CHECK: This account is checked in the instruction handler
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        constraint = mint_config.enabled @ ErrorCode::MintNotEnabled
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
     /// CHECK: This account is checked in the instruction
     #[account(mut)]
     pub to: UncheckedAccount<'info>,
@@ -243,8 +587,8 @@ pub struct Withdraw<'info> {
         seeds = [b"record", sn.as_ref()],
         bump
     )]
-    pub record: Account<'info, TransactionRecord>,
-    pub token_program: Program<'info, Token>,
+    pub record: Option<Account<'info, TransactionRecord>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     /// CHECK: This account is used to verify the Ed25519 instruction
@@ -253,7 +597,7 @@ pub struct Withdraw<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(sn: [u8; 32], account: [u8; 32])]
+#[instruction(sn: [u8; 32], account: [u8; 32], amount: u64, hold_id: [u8; 32])]
 pub struct Freeze<'info> {
     #[account(mut, seeds = [b"payment-state"], bump)]
     pub payment_state: Account<'info, PaymentState>,
@@ -269,6 +613,13 @@ pub struct Freeze<'info> {
     pub user: Signer<'info>,
     /// CHECK: This account is checked in the instruction handler
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
     #[account(
         init_if_needed,
         payer = user,
@@ -276,7 +627,15 @@ pub struct Freeze<'info> {
         seeds = [b"record", sn.as_ref()],
         bump
     )]
-    pub record: Account<'info, TransactionRecord>,
+    pub record: Option<Account<'info, TransactionRecord>>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Hold::LEN,
+        seeds = [b"hold", hold_id.as_ref()],
+        bump
+    )]
+    pub hold: Option<Account<'info, Hold>>,
     pub system_program: Program<'info, System>,
     /// CHECK: This account is used to verify the Ed25519 instruction
     pub instruction_sysvar: AccountInfo<'info>,
@@ -284,7 +643,7 @@ pub struct Freeze<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(sn: [u8; 32], from: [u8; 32])]
+#[instruction(sn: [u8; 32], from: [u8; 32], amount: u64, fee: u64, hold_id: [u8; 32])]
 pub struct Unfreeze<'info> {
     #[account(mut, seeds = [b"payment-state"], bump)]
     pub payment_state: Account<'info, PaymentState>,
@@ -297,7 +656,9 @@ pub struct Unfreeze<'info> {
     )]
     pub user_token_account: Account<'info, UserTokenAccount>,
     #[account(
-        mut,
+        init_if_needed,
+        payer = user,
+        space = 8 + UserTokenAccount::LEN,
         seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
         bump
     )]
@@ -306,6 +667,13 @@ pub struct Unfreeze<'info> {
     pub user: Signer<'info>,
     /// CHECK: This account is checked in the instruction handler
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
     #[account(
         init_if_needed,
         payer = user,
@@ -313,7 +681,13 @@ pub struct Unfreeze<'info> {
         seeds = [b"record", sn.as_ref()],
         bump
     )]
-    pub record: Account<'info, TransactionRecord>,
+    pub record: Option<Account<'info, TransactionRecord>>,
+    #[account(
+        mut,
+        seeds = [b"hold", hold_id.as_ref()],
+        bump
+    )]
+    pub hold: Option<Account<'info, Hold>>,
     pub system_program: Program<'info, System>,
     /// CHECK: This account is used to verify the Ed25519 instruction
     pub instruction_sysvar: AccountInfo<'info>,
@@ -340,7 +714,9 @@ pub struct Transfer<'info> {
     )]
     pub to_token_account: Account<'info, UserTokenAccount>,
     #[account(
-        mut,
+        init_if_needed,
+        payer = user,
+        space = 8 + UserTokenAccount::LEN,
         seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
         bump
     )]
@@ -349,6 +725,20 @@ pub struct Transfer<'info> {
     pub user: Signer<'info>,
     /// CHECK: This account is checked in the instruction handler
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        constraint = mint_config.enabled @ ErrorCode::MintNotEnabled
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
     /// CHECK: This account is checked in the instruction
     #[account(mut)]
     pub out: UncheckedAccount<'info>,
@@ -370,8 +760,8 @@ pub struct Transfer<'info> {
         seeds = [b"record", sn.as_ref()],
         bump
     )]
-    pub record: Account<'info, TransactionRecord>,
-    pub token_program: Program<'info, Token>,
+    pub record: Option<Account<'info, TransactionRecord>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     /// CHECK: This account is used to verify the Ed25519 instruction
@@ -400,7 +790,9 @@ pub struct Settlement<'info> {
     )]
     pub to_token_account: Account<'info, UserTokenAccount>,
     #[account(
-        mut,
+        init_if_needed,
+        payer = user,
+        space = 8 + UserTokenAccount::LEN,
         seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
         bump
     )]
@@ -409,6 +801,20 @@ pub struct Settlement<'info> {
     pub user: Signer<'info>,
     /// CHECK: This account is checked in the instruction handler
     pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        constraint = mint_config.enabled @ ErrorCode::MintNotEnabled
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
     /// CHECK: This account is checked in the instruction
     #[account(mut)]
     pub out: UncheckedAccount<'info>,
@@ -430,11 +836,450 @@ pub struct Settlement<'info> {
         seeds = [b"record", sn.as_ref()],
         bump
     )]
-    pub record: Account<'info, TransactionRecord>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub record: Option<Account<'info, TransactionRecord>>,
+    #[account(
+        mut,
+        seeds = [b"hold", deal.hold_id.as_ref()],
+        bump
+    )]
+    pub hold: Option<Account<'info, Hold>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: This account is used to verify the Ed25519 instruction
+    pub instruction_sysvar: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(sn: [u8; 32], deal: SplitSettlementData)]
+pub struct SettleSplit<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        mut,
+        seeds = [b"user-token", deal.from.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub from_token_account: Account<'info, UserTokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserTokenAccount::LEN,
+        seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, UserTokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        constraint = mint_config.enabled @ ErrorCode::MintNotEnabled
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    /// CHECK: This account is checked in the instruction
+    #[account(
+        mut,
+        seeds = [b"program-token", mint.key().as_ref()],
+        bump,
+        owner = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { system_program.key() } else { token_program.key() }
+    )]
+    pub program_token: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TransactionRecord::LEN,
+        seeds = [b"record", sn.as_ref()],
+        bump
+    )]
+    pub record: Option<Account<'info, TransactionRecord>>,
+    #[account(
+        mut,
+        seeds = [b"hold", deal.hold_id.as_ref()],
+        bump
+    )]
+    pub hold: Option<Account<'info, Hold>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This account is used to verify the Ed25519 instruction
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(sn: [u8; 32])]
+pub struct SettleBatch<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserTokenAccount::LEN,
+        seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, UserTokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        constraint = mint_config.enabled @ ErrorCode::MintNotEnabled
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TransactionRecord::LEN,
+        seeds = [b"record", sn.as_ref()],
+        bump
+    )]
+    pub record: Option<Account<'info, TransactionRecord>>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This account is used to verify the Ed25519 instruction
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(sn: [u8; 32], from: [u8; 32])]
+pub struct TransferMany<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        mut,
+        seeds = [b"user-token", from.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub from_token_account: Account<'info, UserTokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserTokenAccount::LEN,
+        seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, UserTokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump,
+        constraint = mint_config.enabled @ ErrorCode::MintNotEnabled
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TransactionRecord::LEN,
+        seeds = [b"record", sn.as_ref()],
+        bump
+    )]
+    pub record: Option<Account<'info, TransactionRecord>>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This account is used to verify the Ed25519 instruction
+    pub instruction_sysvar: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        mut,
+        seeds = [b"user-token", payment_state.fee_to_account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_token_account: Account<'info, UserTokenAccount>,
+    #[account(mut)]
+    pub fee_to: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    /// CHECK: This account is checked in the instruction
+    #[account(mut)]
+    pub to: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    #[account(
+        mut,
+        seeds = [b"program-token", mint.key().as_ref()],
+        bump,
+        owner = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { system_program.key() } else { token_program.key() }
+    )]
+    pub program_token: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(sn: [u8; 32], account: [u8; 32])]
+pub struct BindWallet<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserTokenAccount::LEN,
+        seeds = [b"user-token", account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_token_account: Account<'info, UserTokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TransactionRecord::LEN,
+        seeds = [b"record", sn.as_ref()],
+        bump
+    )]
+    pub record: Option<Account<'info, TransactionRecord>>,
+    pub system_program: Program<'info, System>,
     /// CHECK: This account is used to verify the Ed25519 instruction
     pub instruction_sysvar: AccountInfo<'info>,
     pub rent: Sysvar<'info, Rent>,
 }
+
+#[derive(Accounts)]
+#[instruction(account: [u8; 32])]
+pub struct RequestExit<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        mut,
+        seeds = [b"user-token", account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_token_account: Account<'info, UserTokenAccount>,
+    pub wallet: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(sn: [u8; 32], account: [u8; 32])]
+pub struct CancelExit<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        mut,
+        seeds = [b"user-token", account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_token_account: Account<'info, UserTokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + TransactionRecord::LEN,
+        seeds = [b"record", sn.as_ref()],
+        bump
+    )]
+    pub record: Option<Account<'info, TransactionRecord>>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: This account is used to verify the Ed25519 instruction
+    pub instruction_sysvar: AccountInfo<'info>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(account: [u8; 32])]
+pub struct ExecuteExit<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    #[account(
+        mut,
+        seeds = [b"user-token", account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_token_account: Account<'info, UserTokenAccount>,
+    pub wallet: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"mint-config", mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    /// CHECK: This account is checked in the instruction
+    #[account(mut)]
+    pub to: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    #[account(
+        mut,
+        seeds = [b"program-token", mint.key().as_ref()],
+        bump,
+        owner = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { system_program.key() } else { token_program.key() }
+    )]
+    pub program_token: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(hold_id: [u8; 32])]
+pub struct ReleaseHold<'info> {
+    #[account(
+        mut,
+        seeds = [b"hold", hold_id.as_ref()],
+        bump,
+        close = payer
+    )]
+    pub hold: Account<'info, Hold>,
+    #[account(
+        mut,
+        seeds = [b"user-token", hold.account.as_ref(), hold.mint.as_ref()],
+        bump
+    )]
+    pub user_token_account: Account<'info, UserTokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"mint-totals", hold.mint.as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    /// CHECK: Receives the rent of the closed hold
+    #[account(mut, address = hold.payer @ ErrorCode::InvalidParameter)]
+    pub payer: UncheckedAccount<'info>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CheckSolvency<'info> {
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    /// CHECK: This account is checked in the instruction
+    #[account(
+        seeds = [b"program-token", mint.key().as_ref()],
+        bump,
+        owner = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { system_program.key() } else { token_program.key() }
+    )]
+    pub program_token: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepSurplus<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    pub owner: Signer<'info>,
+    /// CHECK: This account is checked in the instruction handler
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"mint-totals", mint.key().as_ref()],
+        bump,
+        constraint = mint_totals.seeded @ ErrorCode::TotalsNotSeeded
+    )]
+    pub mint_totals: Account<'info, MintTotals>,
+    /// CHECK: This account is checked in the instruction
+    #[account(mut)]
+    pub to: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    #[account(
+        mut,
+        seeds = [b"program-token", mint.key().as_ref()],
+        bump,
+        owner = if *mint.key == anchor_spl::token::spl_token::native_mint::id() { system_program.key() } else { token_program.key() }
+    )]
+    pub program_token: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRecords<'info> {
+    #[account(seeds = [b"payment-state"], bump)]
+    pub payment_state: Account<'info, PaymentState>,
+    pub authority: Signer<'info>,
+    /// CHECK: Original payer of the records, receives the reclaimed rent. Legacy records carry no
+    /// payer, the owner designates the account their rent goes to
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePaymentState<'info> {
+    /// CHECK: May still have the layout of an older version, checked in the instruction handler
+    #[account(mut, seeds = [b"payment-state"], bump)]
+    pub payment_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(account: [u8; 32])]
+pub struct MigrateUserTokenAccount<'info> {
+    /// CHECK: May still have the layout of an older version, checked in the instruction handler
+    #[account(
+        mut,
+        seeds = [b"user-token", account.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub user_token_account: UncheckedAccount<'info>,
+    /// CHECK: Only used to derive the user-token account
+    pub mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
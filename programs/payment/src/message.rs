@@ -0,0 +1,65 @@
+// Every message signed by the backend starts with this envelope, so a signature can only
+// be used for the operation, program deployment and format version it was made for:
+// MESSAGE_DOMAIN | program id | operation | MESSAGE_VERSION | replay mode | payload
+use sha2::{Digest, Sha256};
+
+pub const MESSAGE_DOMAIN: &[u8] = b"opentaskai-payment";
+pub const MESSAGE_VERSION: u8 = 2;
+
+#[derive(Clone, Copy, Debug)]
+#[repr(u8)]
+pub enum Operation {
+    Deposit = 1,
+    Withdraw = 2,
+    Freeze = 3,
+    Unfreeze = 4,
+    Transfer = 5,
+    Settle = 6,
+    BindWallet = 7,
+    SettleSplit = 8,
+    SettleBatch = 9,
+    TransferMany = 10,
+    AcceptEthSigner = 11,
+    CancelExit = 12,
+}
+
+// Replay protection a message is signed for. A message signed for the record account
+// cannot be executed through the nonce of nosn mode, nor the other way round.
+#[derive(Clone, Copy, Debug)]
+#[repr(u8)]
+pub enum ReplayMode {
+    Record = 0,
+    Nonce = 1,
+}
+
+impl ReplayMode {
+    // Passing the record account selects the record mode, see utils::consume_sn
+    pub fn of<T>(record: &Option<T>) -> Self {
+        if record.is_some() {
+            ReplayMode::Record
+        } else {
+            ReplayMode::Nonce
+        }
+    }
+}
+
+// Commits to a list of items, so one signature can cover a whole batch
+pub fn hash_batch<I: IntoIterator<Item = Vec<u8>>>(items: I) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for item in items {
+        hasher.update(item);
+    }
+    hasher.finalize().into()
+}
+
+pub fn build_message(operation: Operation, replay_mode: ReplayMode, payload: &[&[u8]]) -> Vec<u8> {
+    let mut message = [
+        MESSAGE_DOMAIN,
+        crate::ID.as_ref(),
+        &[operation as u8, MESSAGE_VERSION, replay_mode as u8],
+    ].concat();
+    for part in payload {
+        message.extend_from_slice(part);
+    }
+    message
+}
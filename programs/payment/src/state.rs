@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
 
 #[account]
 #[derive(Default)]
@@ -10,10 +11,72 @@ pub struct PaymentState {
     pub fee_to: Pubkey,
     pub fee_to_account: [u8; 32],
     pub bump: u8,
+    pub paused: u8, // Bitmask of paused operations, see PaymentState::PAUSE_*
+    pub pending_owner: Pubkey, // Proposed owner, becomes owner once it accepts
+    pub pending_signer: Pubkey, // Proposed signer, becomes signer once it accepts
+    pub pending_fee_to: Pubkey, // Proposed fee_to, becomes fee_to once it accepts
+    pub record_retention: i64, // Seconds a record is kept after its expiry before it can be closed
+    pub quorum_signers: Vec<Pubkey>, // Signer set approving high-risk operations
+    pub quorum_threshold: u8, // Approvals required from quorum_signers, 0 disables the quorum
+    pub previous_signer: Pubkey, // Signer replaced by the last rotation
+    pub previous_valid_until: i64, // previous_signer is still accepted before this timestamp
+    pub signer_overlap: i64, // Seconds the previous signer stays valid after a rotation
+    pub signature_scheme: u8, // Scheme of backend signatures, see PaymentState::SCHEME_*
+    pub eth_signer: [u8; 20], // Ethereum address of the signer for SCHEME_SECP256K1
+    pub exit_delay: i64, // Seconds between request_exit and execute_exit, 0 disables exits
+    pub previous_fee_to_account: [u8; 32], // Fee account replaced by the last change, its ledgers can still be migrated
+    pub pending_eth_signer: [u8; 20], // Ethereum address proposed as eth_signer, set once it accepts
+    pub previous_eth_signer: [u8; 20], // eth_signer replaced by the last rotation
+    pub previous_eth_valid_until: i64, // previous_eth_signer is still accepted before this timestamp
 }
 
 impl PaymentState {
-    pub const LEN: usize = 32 + 1 + 1 + 32 + 32 + 32 + 1;
+    pub const MAX_QUORUM_SIGNERS: usize = 10;
+    pub const MAX_SIGNER_OVERLAP: i64 = 7 * 24 * 60 * 60;
+    // Bounds of a non-zero exit_delay: long enough for the backend to cancel an exit
+    pub const MIN_EXIT_DELAY: i64 = 60;
+    pub const MAX_EXIT_DELAY: i64 = 30 * 24 * 60 * 60;
+    pub const LEN: usize = 32 + 1 + 1 + 32 + 32 + 32 + 1 + 1 + 32 + 32 + 32 + 8
+        + 4 + 32 * Self::MAX_QUORUM_SIGNERS + 1 + 32 + 8 + 8 + 1 + 20 + 8 + 32 + 20 + 20 + 8;
+
+    pub const SCHEME_ED25519: u8 = 0;
+    pub const SCHEME_SECP256K1: u8 = 1;
+
+    pub const PAUSE_DEPOSIT: u8 = 1 << 0;
+    pub const PAUSE_WITHDRAW: u8 = 1 << 1;
+    pub const PAUSE_FREEZE: u8 = 1 << 2;
+    pub const PAUSE_UNFREEZE: u8 = 1 << 3;
+    pub const PAUSE_TRANSFER: u8 = 1 << 4;
+    pub const PAUSE_SETTLE: u8 = 1 << 5;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_DEPOSIT
+        | Self::PAUSE_WITHDRAW
+        | Self::PAUSE_FREEZE
+        | Self::PAUSE_UNFREEZE
+        | Self::PAUSE_TRANSFER
+        | Self::PAUSE_SETTLE;
+
+    pub fn is_paused(&self, operation: u8) -> bool {
+        self.paused & operation != 0
+    }
+
+    // Keys whose signatures are accepted at `now`: the signer, plus the previous signer
+    // during the overlap window after a rotation
+    pub fn valid_signers(&self, now: i64) -> Vec<Pubkey> {
+        let mut signers = vec![self.signer];
+        if self.previous_signer != Pubkey::default() && now < self.previous_valid_until {
+            signers.push(self.previous_signer);
+        }
+        signers
+    }
+
+    // Same as valid_signers for the Ethereum addresses of SCHEME_SECP256K1
+    pub fn valid_eth_signers(&self, now: i64) -> Vec<[u8; 20]> {
+        let mut signers = vec![self.eth_signer];
+        if self.previous_eth_signer != [0u8; 20] && now < self.previous_eth_valid_until {
+            signers.push(self.previous_eth_signer);
+        }
+        signers
+    }
 }
 
 #[account]
@@ -23,20 +86,155 @@ pub struct UserTokenAccount {
     pub mint: Pubkey,
     pub available: u64,
     pub frozen: u64,
+    pub nonce: u64, // Last nonce consumed while nosn mode replaces the record account
+    pub wallet: Pubkey, // Wallet bound to the account, allowed to exit without the backend
+    pub exit_requested_at: i64, // Time of the pending exit request, 0 when there is none
+    pub held: u64, // Part of frozen reserved by open holds
 }
 
 impl UserTokenAccount {
-    pub const LEN: usize = 32 + 8 + 8;
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 32 + 8 + 8;
+
+    // Frozen funds not reserved by any hold
+    pub fn unheld_frozen(&self) -> u64 {
+        self.frozen.saturating_sub(self.held)
+    }
+}
+
+// Frozen funds reserved for one order. Once release_at has passed, anyone can
+// return them to the account's available balance.
+#[account]
+#[derive(Default)]
+pub struct Hold {
+    pub account: [u8; 32],
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub beneficiary: [u8; 32],
+    pub release_at: i64,
+    pub payer: Pubkey, // Receives the rent when the hold is closed
+}
+
+impl Hold {
+    pub const LEN: usize = 32 + 32 + 8 + 32 + 8 + 32;
+}
+
+// Per-mint configuration created by the owner; a mint can only be used once it has an
+// enabled config. Limits set to 0 are not enforced.
+#[account]
+#[derive(Default)]
+pub struct MintConfig {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub enabled: bool,
+    pub min_deposit: u64, // Minimum amount of a single deposit
+    pub max_deposit: u64, // Maximum amount of a single deposit
+    pub max_balance: u64, // Maximum available + frozen balance of a single account
+    pub deposit_cap: u64, // Maximum total_deposits
+    pub total_deposits: u64, // Amount deposited into the vault and not paid out yet
+}
+
+impl MintConfig {
+    pub const LEN: usize = 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn check_deposit(&self, amount: u64) -> Result<()> {
+        require!(amount >= self.min_deposit, ErrorCode::DepositTooSmall);
+        require!(self.max_deposit == 0 || amount <= self.max_deposit, ErrorCode::DepositTooLarge);
+        let total_deposits = self.total_deposits.checked_add(amount).ok_or(ErrorCode::DepositCapExceeded)?;
+        require!(self.deposit_cap == 0 || total_deposits <= self.deposit_cap, ErrorCode::DepositCapExceeded);
+        Ok(())
+    }
+
+    pub fn check_balance(&self, account: &UserTokenAccount) -> Result<()> {
+        let balance = account.available.checked_add(account.frozen).ok_or(ErrorCode::BalanceCapExceeded)?;
+        require!(self.max_balance == 0 || balance <= self.max_balance, ErrorCode::BalanceCapExceeded);
+        Ok(())
+    }
+
+    // Records `amount` leaving the vault
+    pub fn sub_deposits(&mut self, amount: u64) -> Result<()> {
+        self.total_deposits = self.total_deposits.checked_sub(amount).ok_or(ErrorCode::InvalidAmount)?;
+        Ok(())
+    }
+}
+
+// Liabilities of the ledger for one mint: the sum of all UserTokenAccount balances.
+// total_fees is the part of total_available credited to the fee ledger.
+// rent_reserve is the part of the SOL vault kept for rent-exemption, never owed to users.
+// Totals created for a vault that already held deposits stay unseeded, and the mint cannot
+// be used, until the owner has set them with seed_mint_totals.
+#[account]
+#[derive(Default)]
+pub struct MintTotals {
+    pub mint: Pubkey,
+    pub total_available: u64,
+    pub total_frozen: u64,
+    pub total_fees: u64,
+    pub rent_reserve: u64,
+    pub seeded: bool,
+}
+
+impl MintTotals {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn liabilities(&self) -> u64 {
+        self.total_available.saturating_add(self.total_frozen)
+    }
+
+    pub fn add_available(&mut self, amount: u64) -> Result<()> {
+        self.total_available = self.total_available.checked_add(amount).ok_or(ErrorCode::InvalidAmount)?;
+        Ok(())
+    }
+
+    pub fn sub_available(&mut self, amount: u64) -> Result<()> {
+        self.total_available = self.total_available.checked_sub(amount).ok_or(ErrorCode::InvalidAmount)?;
+        Ok(())
+    }
+
+    pub fn add_frozen(&mut self, amount: u64) -> Result<()> {
+        self.total_frozen = self.total_frozen.checked_add(amount).ok_or(ErrorCode::InvalidAmount)?;
+        Ok(())
+    }
+
+    pub fn sub_frozen(&mut self, amount: u64) -> Result<()> {
+        self.total_frozen = self.total_frozen.checked_sub(amount).ok_or(ErrorCode::InvalidAmount)?;
+        Ok(())
+    }
+
+    pub fn add_fees(&mut self, amount: u64) -> Result<()> {
+        self.add_available(amount)?;
+        self.total_fees = self.total_fees.checked_add(amount).ok_or(ErrorCode::InvalidAmount)?;
+        Ok(())
+    }
+
+    pub fn sub_fees(&mut self, amount: u64) -> Result<()> {
+        self.sub_available(amount)?;
+        // Only fees credited through add_fees can be claimed
+        self.total_fees = self.total_fees.checked_sub(amount).ok_or(ErrorCode::InvalidAmount)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MintConfigParams {
+    pub enabled: bool,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+    pub max_balance: u64,
+    pub deposit_cap: u64,
 }
 
 #[account]
 #[derive(Default)]
 pub struct TransactionRecord {
     pub executed: bool,
+    pub expired_at: i64, // Expiry of the signed message, the sn cannot be replayed after it
+    pub payer: Pubkey, // Account that paid the rent, refunded when the record is closed
 }
 
 impl TransactionRecord {
-    pub const LEN: usize = 1;
+    pub const LEN: usize = 1 + 8 + 32;
+    // Records written before expiry and payer tracking only held `executed`
+    pub const LEGACY_LEN: usize = 1;
 }
 
 #[derive(Debug)]
@@ -50,9 +248,20 @@ pub struct SettlementData {
     pub fee: u64, // Base fee for the transaction transferred to the fee account
     pub paid: u64, // Total amount paid by the sender, potentially including excess payment, which is frozen in the sender's account
     pub excess_fee: u64, // Additional fee charged if 'paid' exceeds 'frozen', transferred to the fee account
+    pub hold_id: [u8; 32], // Hold the frozen part is drawn from, zero for funds frozen without a hold
 }
 
 impl SettlementData {
+    // The amounts taken from the sender must add up to what is paid out
+    pub fn is_valid(&self) -> bool {
+        self.available + self.frozen == self.amount + self.fee &&
+        self.amount + self.fee > 0 &&
+        self.paid >= self.frozen + self.excess_fee &&
+        self.frozen >= self.excess_fee &&
+        // Only the frozen part of a deal can come from a hold
+        (self.frozen > 0 || self.hold_id == [0u8; 32])
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         [
             &self.from[..],
@@ -63,7 +272,74 @@ impl SettlementData {
             &self.fee.to_le_bytes(),
             &self.paid.to_le_bytes(),
             &self.excess_fee.to_le_bytes(),
+            &self.hold_id[..],
+        ]
+        .concat()
+    }
+}
+
+// One leg of a split settlement: credited to the ledger of `account`, or paid out to
+// the external `out` account when it is set
+#[derive(Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SplitPayee {
+    pub account: [u8; 32], // Recipient's account number, used when out is not set
+    pub out: Pubkey, // External account paid directly
+    pub amount: u64,
+}
+
+impl SplitPayee {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            &self.account[..],
+            &self.out.to_bytes()[..],
+            &self.amount.to_le_bytes(),
         ]
         .concat()
     }
 }
+
+#[derive(Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SplitSettlementData {
+    pub from: [u8; 32], // Sender's account number
+    pub available: u64, // Amount deducted from sender's available balance
+    pub frozen: u64, // Amount deducted from sender's frozen balance
+    pub fee: u64, // Fee credited to the fee account
+    pub hold_id: [u8; 32], // Hold the frozen part is drawn from, zero for funds frozen without a hold
+    pub payees: Vec<SplitPayee>, // Each payee needs its ledger account or `out` in remaining_accounts, in order
+}
+
+impl SplitSettlementData {
+    pub const MAX_PAYEES: usize = 8;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = [
+            &self.from[..],
+            &self.available.to_le_bytes(),
+            &self.frozen.to_le_bytes(),
+            &self.fee.to_le_bytes(),
+            &self.hold_id[..],
+            &[self.payees.len() as u8],
+        ]
+        .concat();
+        for payee in &self.payees {
+            bytes.extend_from_slice(&payee.to_bytes());
+        }
+        bytes
+    }
+}
+
+// One credit of a transfer_many fan-out
+#[derive(Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransferRecipient {
+    pub to: [u8; 32], // Recipient's account number
+    pub amount: u64, // Amount credited to the recipient's available balance
+}
+
+impl TransferRecipient {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.to[..], &self.amount.to_le_bytes()].concat()
+    }
+}
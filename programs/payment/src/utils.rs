@@ -1,12 +1,18 @@
 use anchor_lang::prelude::*;
 use crate::errors::ErrorCode;
 use anchor_lang::system_program::System;
-use anchor_spl::token::{self, Token};
-use crate::state::{PaymentState};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{self, Mint, TokenInterface, TransferChecked};
+use solana_program::program_pack::Pack;
+use crate::state::{PaymentState, TransactionRecord, UserTokenAccount, Hold};
 use sha2::{Sha256, Digest};
-// use solana_program::ed25519_program;
+use solana_program::{ed25519_program, secp256k1_program};
 // use solana_program::instruction::Instruction;
-use solana_program::sysvar::instructions::{load_instruction_at_checked, load_current_index_checked};
+use solana_program::sysvar::instructions::load_instruction_at_checked;
 
 fn hash_sha256(data: &[u8]) -> [u8; 32] {
     let mut hasher = Sha256::new();
@@ -14,10 +20,94 @@ fn hash_sha256(data: &[u8]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
+// Replay protection for a signed operation. With a record account the sn can only be
+// executed once. In nosn mode the record may be omitted and the first 8 bytes of sn
+// carry a little-endian nonce that must be greater than the account's last nonce.
+// The signed message commits to the mode (see message::ReplayMode), so an sn executed
+// through one mode cannot be replayed through the other.
+pub fn consume_sn<'info>(
+    payment_state: &Account<'info, PaymentState>,
+    record: &mut Option<Account<'info, TransactionRecord>>,
+    user_token_account: &mut Account<'info, UserTokenAccount>,
+    sn: &[u8; 32],
+    expired_at: i64,
+    payer: Pubkey,
+) -> Result<()> {
+    match record {
+        Some(record) => {
+            require!(!record.executed, ErrorCode::AlreadyExecuted);
+            record.executed = true;
+            // Remember who paid the rent and when the signature stops being valid,
+            // so the record can be closed once it can no longer be replayed
+            record.expired_at = expired_at;
+            record.payer = payer;
+        }
+        None => {
+            require!(payment_state.nosn_enabled, ErrorCode::MissingRecord);
+            let nonce = u64::from_le_bytes(sn[..8].try_into().unwrap());
+            require!(nonce > user_token_account.nonce, ErrorCode::InvalidNonce);
+            user_token_account.nonce = nonce;
+        }
+    }
+    Ok(())
+}
+
+// Takes `amount` out of the frozen funds reserved by `hold_id`, or out of the frozen
+// funds no hold reserves when hold_id is zero. Only `held` is adjusted here, the
+// caller still moves the funds out of `frozen`.
+pub fn draw_hold(
+    hold: &mut Option<Account<Hold>>,
+    user_token_account: &mut UserTokenAccount,
+    hold_id: &[u8; 32],
+    account: &[u8; 32],
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if *hold_id == [0u8; 32] {
+        require!(hold.is_none(), ErrorCode::InvalidParameter);
+        require!(user_token_account.unheld_frozen() >= amount, ErrorCode::InsufficientFrozen);
+        return Ok(());
+    }
+
+    let hold = hold.as_mut().ok_or(ErrorCode::MissingHold)?;
+    require!(hold.account == *account && hold.mint == *mint, ErrorCode::InvalidParameter);
+    require!(hold.amount >= amount, ErrorCode::InsufficientFrozen);
+    hold.amount -= amount;
+    user_token_account.held = user_token_account.held.checked_sub(amount).ok_or(ErrorCode::InsufficientFrozen)?;
+    Ok(())
+}
+
+// Verifies the backend signature over `message` with the scheme the deployment is
+// configured for: Ed25519 by the signer (or the previous signer during a rotation), or
+// Secp256k1 by the Ethereum address in eth_signer.
+pub fn verify_signature(
+    instruction_sysvar: &AccountInfo,
+    payment_state: &PaymentState,
+    now: i64,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    match payment_state.signature_scheme {
+        PaymentState::SCHEME_ED25519 => verify_ed25519_instruction(
+            instruction_sysvar,
+            &payment_state.valid_signers(now),
+            message,
+            signature,
+        ),
+        PaymentState::SCHEME_SECP256K1 => verify_secp256k1_instruction(
+            instruction_sysvar,
+            &payment_state.valid_eth_signers(now),
+            message,
+            signature,
+        ),
+        _ => Err(ErrorCode::InvalidSignatureScheme.into()),
+    }
+}
+
 #[cfg(feature = "test")]
 pub fn verify_ed25519_instruction(
     instruction_sysvar: &AccountInfo,
-    expected_public_key: &[u8],
+    expected_public_keys: &[Pubkey],
     message: &[u8],
     signature: &[u8]
 ) -> Result<()> {
@@ -25,59 +115,249 @@ pub fn verify_ed25519_instruction(
     Ok(())
 }
 
+// Scans the transaction for an Ed25519 program instruction that verified `signature`
+// by one of `expected_public_keys` over the hash of `message`. The proof may sit anywhere
+// in the transaction, and one instruction with several signatures may cover several
+// payment instructions.
 #[cfg(not(feature = "test"))]
 pub fn verify_ed25519_instruction(
     instruction_sysvar: &AccountInfo,
-    expected_public_key: &[u8],
+    expected_public_keys: &[Pubkey],
     message: &[u8],
     signature: &[u8]
 ) -> Result<()> {
-    let current_index = load_current_index_checked(instruction_sysvar)?;
-    if current_index == 0 {
-        return Err(ErrorCode::MissingEd25519Instruction.into());
-    }
+    let message_hash = hash_sha256(message);
 
-    let ed25519_instruction = load_instruction_at_checked((current_index - 1) as usize, instruction_sysvar)?;
-    
-    // Verify the content of the Ed25519 instruction
-    let instruction_data = ed25519_instruction.data;
-    if instruction_data.len() < 2 {
-        return Err(ErrorCode::InvalidEd25519Instruction.into());
-    }
+    // Most specific mismatch seen so far, reported if no signature matches
+    let mut mismatch: Option<ErrorCode> = None;
+    let found = for_each_ed25519_signature(instruction_sysvar, |pubkey, signed_message, signed_signature| {
+        if !expected_public_keys.iter().any(|key| key.as_ref() == pubkey) {
+            mismatch = mismatch.or(Some(ErrorCode::InvalidPublicKey));
+            return false;
+        }
+        if signed_message != message_hash {
+            if !matches!(mismatch, Some(ErrorCode::InvalidSignature)) {
+                mismatch = Some(ErrorCode::InvalidMessage);
+            }
+            return false;
+        }
+        if signed_signature != signature {
+            mismatch = Some(ErrorCode::InvalidSignature);
+            return false;
+        }
+        true
+    })?;
 
-    let num_signatures = instruction_data[0];
-    if num_signatures != 1 {
-        return Err(ErrorCode::InvalidEd25519Instruction.into());
+    if found {
+        return Ok(());
     }
+    Err(mismatch.unwrap_or(ErrorCode::MissingEd25519Instruction).into())
+}
 
-    // Parse Ed25519SignatureOffsets
-    let offsets: Ed25519SignatureOffsets = Ed25519SignatureOffsets::try_from_slice(&instruction_data[2..16])?;
+#[cfg(feature = "test")]
+pub fn verify_quorum(
+    instruction_sysvar: &AccountInfo,
+    payment_state: &PaymentState,
+    message: &[u8],
+) -> Result<()> {
+    msg!("Quorum verification skipped in test mode");
+    Ok(())
+}
 
-    // Verify public key
-    let pubkey_start = offsets.public_key_offset as usize;
-    let pubkey_end = pubkey_start + 32;
-    if &instruction_data[pubkey_start..pubkey_end] != expected_public_key {
-        return Err(ErrorCode::InvalidPublicKey.into());
+// Requires `quorum_threshold` distinct keys of the quorum signer set to have signed the
+// hash of `message` through Ed25519 program instructions in this transaction. The
+// precompile has already checked every signature, so matching key and message is enough.
+#[cfg(not(feature = "test"))]
+pub fn verify_quorum(
+    instruction_sysvar: &AccountInfo,
+    payment_state: &PaymentState,
+    message: &[u8],
+) -> Result<()> {
+    let threshold = payment_state.quorum_threshold as usize;
+    if threshold == 0 {
+        return Ok(());
     }
+    // Approvals are only collected from Ed25519 instructions
+    require!(payment_state.signature_scheme == PaymentState::SCHEME_ED25519, ErrorCode::QuorumRequiresEd25519);
 
-    // Verify message
     let message_hash = hash_sha256(message);
-    let msg_start = offsets.message_data_offset as usize;
-    let msg_end = msg_start + offsets.message_data_size as usize;
-    if &instruction_data[msg_start..msg_end] != message_hash {
-        return Err(ErrorCode::InvalidMessage.into());
-    }
+    let mut approved = vec![false; payment_state.quorum_signers.len()];
+    let mut approvals = 0;
+    for_each_ed25519_signature(instruction_sysvar, |pubkey, signed_message, _| {
+        if signed_message != message_hash {
+            return false;
+        }
+        if let Some(i) = payment_state.quorum_signers.iter().position(|signer| signer.as_ref() == pubkey) {
+            if !approved[i] {
+                approved[i] = true;
+                approvals += 1;
+            }
+        }
+        approvals >= threshold
+    })?;
+
+    require!(approvals >= threshold, ErrorCode::InsufficientApprovals);
+    Ok(())
+}
 
-    // Verify signature
-    let sig_start = offsets.signature_offset as usize;
-    let sig_end = sig_start + 64;
-    if &instruction_data[sig_start..sig_end] != signature {
-        return Err(ErrorCode::InvalidSignature.into());
+// Calls `visit` with the public key, message and signature of every signature verified by
+// an Ed25519 program instruction in the transaction, until it returns true.
+// Returns whether `visit` returned true. Entries that are malformed or not self-contained
+// are skipped, so other signature checks bundled in the same transaction do not interfere.
+fn for_each_ed25519_signature(
+    instruction_sysvar: &AccountInfo,
+    mut visit: impl FnMut(&[u8], &[u8], &[u8]) -> bool,
+) -> Result<bool> {
+    let num_instructions = load_num_instructions(instruction_sysvar)?;
+    for index in 0..num_instructions {
+        let instruction = load_instruction_at_checked(index as usize, instruction_sysvar)?;
+        if instruction.program_id != ed25519_program::id() {
+            continue;
+        }
+
+        let instruction_data = instruction.data;
+        let num_signatures = instruction_data.first().copied().unwrap_or(0) as usize;
+        for i in 0..num_signatures {
+            // Parse Ed25519SignatureOffsets
+            let offsets_start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_SIZE;
+            let Some(offsets) = instruction_data
+                .get(offsets_start..offsets_start + ED25519_OFFSETS_SIZE)
+                .and_then(|data| Ed25519SignatureOffsets::try_from_slice(data).ok())
+            else {
+                break;
+            };
+
+            // All data must live in the Ed25519 instruction itself, otherwise the precompile
+            // may have verified bytes from another instruction than the ones checked here
+            let self_contained = [
+                offsets.signature_instruction_index,
+                offsets.public_key_instruction_index,
+                offsets.message_instruction_index,
+            ]
+            .iter()
+            .all(|&data_index| data_index == u16::MAX || data_index == index);
+            if !self_contained {
+                continue;
+            }
+
+            let (Some(pubkey), Some(signed_message), Some(signed_signature)) = (
+                read_instruction_data(&instruction_data, offsets.public_key_offset, 32),
+                read_instruction_data(&instruction_data, offsets.message_data_offset, offsets.message_data_size),
+                read_instruction_data(&instruction_data, offsets.signature_offset, 64),
+            ) else {
+                continue;
+            };
+            if visit(pubkey, signed_message, signed_signature) {
+                return Ok(true);
+            }
+        }
     }
+    Ok(false)
+}
 
+#[cfg(feature = "test")]
+pub fn verify_secp256k1_instruction(
+    instruction_sysvar: &AccountInfo,
+    expected_eth_addresses: &[[u8; 20]],
+    message: &[u8],
+    signature: &[u8]
+) -> Result<()> {
+    msg!("Signature verification skipped in test mode");
     Ok(())
 }
 
+// Scans the transaction for a Secp256k1 program instruction that recovered one of
+// `expected_eth_addresses` from `signature` over the hash of `message`. The message layout
+// is the same as for Ed25519: the signed data is the sha256 of the message, which the
+// precompile hashes again with keccak256 before recovering the address.
+#[cfg(not(feature = "test"))]
+pub fn verify_secp256k1_instruction(
+    instruction_sysvar: &AccountInfo,
+    expected_eth_addresses: &[[u8; 20]],
+    message: &[u8],
+    signature: &[u8]
+) -> Result<()> {
+    let message_hash = hash_sha256(message);
+    let num_instructions = load_num_instructions(instruction_sysvar)?;
+
+    // Most specific mismatch seen so far, reported if no signature matches
+    let mut mismatch: Option<ErrorCode> = None;
+    for index in 0..num_instructions {
+        let instruction = load_instruction_at_checked(index as usize, instruction_sysvar)?;
+        if instruction.program_id != secp256k1_program::id() {
+            continue;
+        }
+
+        // Malformed or not self-contained entries belong to some other check and are skipped
+        let instruction_data = instruction.data;
+        let num_signatures = instruction_data.first().copied().unwrap_or(0) as usize;
+        for i in 0..num_signatures {
+            // Parse SecpSignatureOffsets
+            let offsets_start = SECP256K1_OFFSETS_START + i * SECP256K1_OFFSETS_SIZE;
+            let Some(offsets) = instruction_data
+                .get(offsets_start..offsets_start + SECP256K1_OFFSETS_SIZE)
+                .and_then(|data| SecpSignatureOffsets::try_from_slice(data).ok())
+            else {
+                break;
+            };
+
+            // All data must live in the Secp256k1 instruction itself
+            let self_contained = [
+                offsets.signature_instruction_index,
+                offsets.eth_address_instruction_index,
+                offsets.message_instruction_index,
+            ]
+            .iter()
+            .all(|&data_index| data_index as u16 == index);
+            if !self_contained {
+                continue;
+            }
+
+            let (Some(eth_address), Some(signed_message), Some(signed_signature)) = (
+                read_instruction_data(&instruction_data, offsets.eth_address_offset, 20),
+                read_instruction_data(&instruction_data, offsets.message_data_offset, offsets.message_data_size),
+                // The recovery id following the 64 signature bytes is not part of `signature`
+                read_instruction_data(&instruction_data, offsets.signature_offset, 64),
+            ) else {
+                continue;
+            };
+
+            if !expected_eth_addresses.iter().any(|address| address.as_ref() == eth_address) {
+                mismatch = mismatch.or(Some(ErrorCode::InvalidPublicKey));
+                continue;
+            }
+            if signed_message != message_hash {
+                if !matches!(mismatch, Some(ErrorCode::InvalidSignature)) {
+                    mismatch = Some(ErrorCode::InvalidMessage);
+                }
+                continue;
+            }
+            if signed_signature != signature {
+                mismatch = Some(ErrorCode::InvalidSignature);
+                continue;
+            }
+
+            return Ok(());
+        }
+    }
+
+    Err(mismatch.unwrap_or(ErrorCode::MissingSecp256k1Instruction).into())
+}
+
+// The instructions sysvar starts with the number of instructions as a little-endian u16
+fn load_num_instructions(instruction_sysvar: &AccountInfo) -> Result<u16> {
+    require_keys_eq!(*instruction_sysvar.key, solana_program::sysvar::instructions::id(), ErrorCode::InvalidParameter);
+    let data = instruction_sysvar.try_borrow_data()?;
+    let bytes = data.get(0..2).ok_or(ErrorCode::InvalidParameter)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_instruction_data(instruction_data: &[u8], offset: u16, size: u16) -> Option<&[u8]> {
+    let start = offset as usize;
+    let end = start + size as usize;
+    instruction_data.get(start..end)
+}
+
 // pub fn create_ed25519_instruction(
 //     public_key: &[u8],
 //     message: &[u8],
@@ -107,6 +387,22 @@ pub fn verify_ed25519_instruction(
 //     }
 // }
 
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_SIZE: usize = 14;
+const SECP256K1_OFFSETS_START: usize = 1;
+const SECP256K1_OFFSETS_SIZE: usize = 11;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct SecpSignatureOffsets {
+    signature_offset: u16,
+    signature_instruction_index: u8,
+    eth_address_offset: u16,
+    eth_address_instruction_index: u8,
+    message_data_offset: u16,
+    message_data_size: u16,
+    message_instruction_index: u8,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 struct Ed25519SignatureOffsets {
     signature_offset: u16,
@@ -120,11 +416,15 @@ struct Ed25519SignatureOffsets {
 
 pub fn transfer_sol<'info>(
     program_token: &UncheckedAccount<'info>,
-    to: &UncheckedAccount<'info>,
+    to: &AccountInfo<'info>,
     system_program: &Program<'info, System>,
     bump: u8,
     amount: u64,
 ) -> Result<()> {
+    // Never dip into the rent reserve, the runtime would reject the transaction anyway
+    let remaining = program_token.lamports().checked_sub(amount).ok_or(ErrorCode::InsufficientFunds)?;
+    require!(remaining >= rent_reserve(program_token)?, ErrorCode::VaultRentReserve);
+
     let mint_key = anchor_spl::token::spl_token::native_mint::id();
     let seeds = &[
         b"program-token",
@@ -149,31 +449,220 @@ pub fn transfer_sol<'info>(
     Ok(())
 }
 
+// Lamports the SOL vault must always hold to stay rent-exempt
+pub fn rent_reserve(program_token: &AccountInfo) -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(program_token.data_len()))
+}
+
+// Balance of the vault that can back ledger liabilities. For SOL the rent
+// reserve is excluded.
+pub fn vault_assets(mint: &AccountInfo, program_token: &AccountInfo) -> Result<u64> {
+    if mint.key() == native_mint::id() {
+        Ok(program_token.lamports().saturating_sub(rent_reserve(program_token)?))
+    } else {
+        let vault = token_interface::TokenAccount::try_deserialize(&mut &program_token.data.borrow()[..])?;
+        require!(vault.mint == mint.key(), ErrorCode::InvalidProgramToken);
+        Ok(vault.amount)
+    }
+}
+
+// Creates the PDA `target` owned by `owner`. Unlike a plain create_account this also
+// works when lamports were already sent to the address.
+pub fn create_pda_account<'info>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let shortfall = rent.saturating_sub(target.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Allocate { account_to_allocate: target.clone() },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Assign { account_to_assign: target.clone() },
+            &[seeds],
+        ),
+        owner,
+    )
+}
+
+// Reallocates a program account to `space` bytes after an upgrade appended fields to its
+// layout, topping up its rent from `payer`. The new bytes are zeroed, which is the default
+// of every appended field. Accounts already at that size are left untouched.
+pub fn grow_account<'info>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if target.data_len() >= space {
+        return Ok(());
+    }
+    let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(target.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: target.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    target.realloc(space, true)?;
+    Ok(())
+}
+
+// Loads the ledger of (`account`, `mint`) passed through remaining_accounts, creating
+// it at `payer`'s expense when it does not exist yet. Changes are only persisted once
+// the caller calls exit() on the result.
+pub fn load_user_token_account<'info>(
+    info: &'info AccountInfo<'info>,
+    account: &[u8; 32],
+    mint: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<Account<'info, UserTokenAccount>> {
+    let (expected, bump) = Pubkey::find_program_address(&[b"user-token", account, mint.as_ref()], &crate::ID);
+    require!(info.key() == expected && info.is_writable, ErrorCode::InvalidUserTokenAccount);
+
+    if *info.owner == System::id() {
+        create_pda_account(
+            info,
+            payer,
+            system_program,
+            8 + UserTokenAccount::LEN,
+            &crate::ID,
+            &[b"user-token", account, mint.as_ref(), &[bump]],
+        )?;
+        info.try_borrow_mut_data()?[..8].copy_from_slice(&<UserTokenAccount as anchor_lang::Discriminator>::DISCRIMINATOR);
+    }
+
+    let mut user_token_account = Account::<UserTokenAccount>::try_from(info)?;
+    if user_token_account.mint == Pubkey::default() {
+        user_token_account.mint = *mint;
+    }
+    Ok(user_token_account)
+}
+
+// A SOL payout may target a wSOL token account, in which case the lamports
+// sent by transfer_sol have to be synced into its token amount
+pub fn sync_if_wrapped<'info>(
+    to: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    if *to.owner != token_program.key() {
+        return Ok(());
+    }
+    let account = token_interface::TokenAccount::try_deserialize(&mut &to.data.borrow()[..])?;
+    require!(account.mint == native_mint::id(), ErrorCode::InvalidMint);
+    token_interface::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        token_interface::SyncNative { account: to.to_account_info() },
+    ))
+}
+
+// Pays `amount` out of the program token vault and returns the amount the recipient
+// receives, which is less when a Token-2022 transfer fee is withheld
 pub fn transfer_token<'info>(
     program_token: &UncheckedAccount<'info>,
-    to: &UncheckedAccount<'info>,
-    token_program: &Program<'info, Token>,
+    to: &AccountInfo<'info>,
+    mint: &UncheckedAccount<'info>,
+    token_program: &Interface<'info, TokenInterface>,
     authority: &Account<'info, PaymentState>,
     bump: u8,
     amount: u64,
-) -> Result<()> {
+) -> Result<u64> {
     let seeds = &[
         b"payment-state".as_ref(),
         &[bump],
     ];
-    token::transfer(
+    let decimals = get_mint_decimals(mint, token_program)?;
+    let fee = get_transfer_fee(mint, amount)?;
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
-            token::Transfer {
+            TransferChecked {
                 from: program_token.to_account_info(),
+                mint: mint.to_account_info(),
                 to: to.to_account_info(),
                 authority: authority.to_account_info(),
             },
             &[seeds],
         ),
-        amount
+        amount,
+        decimals,
     )?;
-    Ok(())
+    Ok(amount - fee)
+}
+
+// Reads the decimals of a mint owned by either the token program or Token-2022
+pub fn get_mint_decimals<'info>(
+    mint: &UncheckedAccount<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<u8> {
+    require_keys_eq!(*mint.owner, token_program.key(), ErrorCode::InvalidMint);
+    let mint_data = Mint::try_deserialize(&mut &mint.data.borrow()[..])?;
+    Ok(mint_data.decimals)
+}
+
+// Fee a Token-2022 mint with the transfer fee extension withholds when `amount` is
+// transferred in the current epoch, zero for any other mint
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::InvalidAmount)?,
+        Err(_) => 0,
+    };
+    Ok(fee)
+}
+
+// Space of the program-token vault for `mint`: nothing for SOL, a plain token account for
+// the token program, and a token account with the extensions the mint requires for Token-2022
+pub fn program_token_space(mint: &AccountInfo) -> usize {
+    if *mint.key == native_mint::id() {
+        return 0;
+    }
+    if *mint.owner != spl_token_2022::ID {
+        return spl_token_2022::state::Account::LEN;
+    }
+    let account_len = || -> std::result::Result<usize, ProgramError> {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        let account_extensions = ExtensionType::get_required_init_account_extensions(&mint_state.get_extension_types()?);
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&account_extensions)
+    };
+    account_len().unwrap_or(spl_token_2022::state::Account::LEN)
 }
 
 // Public function to get the owner of the associated token account
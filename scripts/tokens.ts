@@ -52,7 +52,7 @@ export async function getTokenInfo(connection: web3.Connection, mint: web3.Publi
   return await token.getMint(connection, mint);
 } 
 
-export async function getTokenAccountBalance(connection: web3.Connection, mint: web3.PublicKey, owner: web3.PublicKey) {
+export async function getTokenAccountBalance(connection: web3.Connection, mint: web3.PublicKey, owner: web3.PublicKey, programId: web3.PublicKey = token.TOKEN_PROGRAM_ID) {
   try {
     // Check if the mint is the native SOL mint
     if (mint.toBase58() === token.NATIVE_MINT.toBase58()) {
@@ -62,7 +62,9 @@ export async function getTokenAccountBalance(connection: web3.Connection, mint:
       // Find the associated token account address
       const associatedTokenAddress = await token.getAssociatedTokenAddress(
         mint,
-        owner
+        owner,
+        false,
+        programId
       );
       // Attempt to get the token account balance
       const balance = await connection.getTokenAccountBalance(associatedTokenAddress);
@@ -80,7 +82,7 @@ export async function getTokenAccountBalance(connection: web3.Connection, mint:
   }
 }
 
-export async function getPDABalance(connection: web3.Connection, mint: web3.PublicKey, pda: web3.PublicKey) {
+export async function getPDABalance(connection: web3.Connection, mint: web3.PublicKey, pda: web3.PublicKey, programId: web3.PublicKey = token.TOKEN_PROGRAM_ID) {
   try {
     // Check if the mint is the native SOL mint
     if(mint.toBase58() === token.NATIVE_MINT.toBase58()) {
@@ -89,7 +91,7 @@ export async function getPDABalance(connection: web3.Connection, mint: web3.Publ
       return BigInt(balance);
     } else {
       // Fetch the token account associated with the PDA
-      const balance = await token.getAccount(connection, pda);
+      const balance = await token.getAccount(connection, pda, undefined, programId);
       console.log("Token Balance:", balance);
       return BigInt(balance.amount);
     }
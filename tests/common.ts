@@ -9,7 +9,10 @@ import {
   LAMPORTS_PER_SOL,
   SystemProgram,
   SYSVAR_INSTRUCTIONS_PUBKEY,
-  Ed25519Program
+  Ed25519Program,
+  Secp256k1Program,
+  Transaction,
+  sendAndConfirmTransaction
 } from "@solana/web3.js";
 import * as secp256k1 from 'secp256k1';
 import { keccak256 } from 'js-sha3';
@@ -19,6 +22,53 @@ import assert from "assert";
 import { createHash } from 'crypto';
 
 export const FEE_ACCOUNT_FILL = '00000000000000000000000000000001';
+
+// Signed message envelope, must match programs/payment/src/message.rs
+export const MESSAGE_DOMAIN = Buffer.from("opentaskai-payment");
+export const MESSAGE_VERSION = 2;
+export enum Operation {
+  Deposit = 1,
+  Withdraw = 2,
+  Freeze = 3,
+  Unfreeze = 4,
+  Transfer = 5,
+  Settle = 6,
+  BindWallet = 7,
+  SettleSplit = 8,
+  SettleBatch = 9,
+  TransferMany = 10,
+  AcceptEthSigner = 11,
+  CancelExit = 12,
+}
+
+// Must match PaymentState::SCHEME_* in programs/payment/src/state.rs
+export enum SignatureScheme {
+  Ed25519 = 0,
+  Secp256k1 = 1,
+}
+
+// A zero hold id refers to frozen funds no hold reserves, so no hold account is passed
+export function holdPDA(program: Program<Payment>, holdId: Buffer): PublicKey | null {
+  if (holdId.equals(Buffer.alloc(32))) {
+    return null;
+  }
+  return PublicKey.findProgramAddressSync([Buffer.from("hold"), holdId], program.programId)[0];
+}
+
+// Record when the record account is passed, Nonce when nosn mode replaces it
+export enum ReplayMode {
+  Record = 0,
+  Nonce = 1,
+}
+
+export function buildMessage(programId: PublicKey, operation: Operation, payload: Buffer[], replayMode: ReplayMode = ReplayMode.Record) {
+  return Buffer.concat([
+    MESSAGE_DOMAIN,
+    programId.toBuffer(),
+    Buffer.from([operation, MESSAGE_VERSION, replayMode]),
+    ...payload,
+  ]);
+}
 export const ZERO_ACCOUNT = new PublicKey(new Uint8Array(32).fill(0));
 console.log("Zero PublicKey:", ZERO_ACCOUNT.toBase58());
 
@@ -42,6 +92,29 @@ export async function getTransactionFee(provider: anchor.AnchorProvider, txSigna
   return txDetails.meta.fee;
 }
 
+// Unix timestamp of the latest block, which is what the program sees as the clock
+export async function getChainTime(provider: anchor.AnchorProvider) {
+  const slot = await provider.connection.getSlot();
+  return (await provider.connection.getBlockTime(slot)) ?? 0;
+}
+
+export async function waitForChainTime(provider: anchor.AnchorProvider, timestamp: number) {
+  while ((await getChainTime(provider)) <= timestamp) {
+    await new Promise(resolve => setTimeout(resolve, 500));
+  }
+}
+
+// Asserts that the submission fails with the given Anchor error code
+export async function expectError(submission: Promise<any>, code: string) {
+  try {
+    await submission;
+    assert.fail("Expected an error but the transaction succeeded");
+  } catch (error) {
+    assert.ok(error instanceof anchor.AnchorError);
+    assert.strictEqual(error.error.errorCode.code, code);
+  }
+}
+
 export async function checkTransactionExecuted(provider: anchor.AnchorProvider, program: Program<Payment>, sn: string) {
   // Derive the PDA for the TransactionRecord
   const [transactionRecordPDA] = PublicKey.findProgramAddressSync(
@@ -118,6 +191,37 @@ export function signMessageForSecp256k1(message: Buffer, signerKeypair: Keypair)
   return fullSignature;
 }
 
+export function ethAddressOf(signerKeypair: Keypair) {
+  const publicKey = secp256k1.publicKeyCreate(signerKeypair.secretKey.slice(0, 32), false);
+  return Buffer.from(keccak256(publicKey.slice(1)), 'hex').slice(-20);
+}
+
+// The program expects the sha256 of the message to be signed, the precompile hashes it
+// again with keccak256. `instructionIndex` is the position of the instruction in the transaction.
+export function getSecp256k1Instruction(message: Buffer, signerKeypair: Keypair, instructionIndex: number = 0) {
+  const messageHash = createHash('sha256').update(message).digest();
+  const fullSignature = signMessageForSecp256k1(messageHash, signerKeypair);
+  const signature = fullSignature.subarray(0, 64);
+  const secp256k1Instruction = Secp256k1Program.createInstructionWithEthAddress({
+    ethAddress: ethAddressOf(signerKeypair),
+    message: messageHash,
+    signature,
+    recoveryId: fullSignature[64],
+    instructionIndex,
+  });
+  return {secp256k1Instruction, signature};
+}
+
+// Signs the message with `signerKeypair` under the given scheme
+export function getSignatureInstruction(message: Buffer, signerKeypair: Keypair, scheme: SignatureScheme) {
+  if (scheme === SignatureScheme.Secp256k1) {
+    const {secp256k1Instruction, signature} = getSecp256k1Instruction(message, signerKeypair);
+    return {instruction: secp256k1Instruction, signature};
+  }
+  const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
+  return {instruction: ed25519Instruction, signature};
+}
+
 export async function depositSol(
   provider: anchor.AnchorProvider,
   program: Program<Payment>,
@@ -159,7 +263,8 @@ export async function depositWithMessage(
   amount: anchor.BN,
   frozen: anchor.BN,
   expiredAt: anchor.BN,
-  message: Buffer = null
+  message: Buffer = null,
+  tokenProgram: PublicKey = spl.TOKEN_PROGRAM_ID
 ) {
   const accountBuffer = bytes32Buffer(account);
   const snBuffer = bytes32Buffer(sn);
@@ -207,13 +312,19 @@ export async function depositWithMessage(
       provider.connection,
       payerKeypair,
       mint,
-      payerKeypair.publicKey
+      payerKeypair.publicKey,
+      false,
+      undefined,
+      undefined,
+      tokenProgram
     );
     tokenAccount = userTokenAccount.address;
 
     const associatedTokenAddress = await spl.getAssociatedTokenAddress(
         mint,
-        payerKeypair.publicKey
+        payerKeypair.publicKey,
+        false,
+        tokenProgram
       );
 
     assert.strictEqual(tokenAccount.toBase58(), associatedTokenAddress.toBase58(),"payer token public key does not match");
@@ -225,7 +336,7 @@ export async function depositWithMessage(
 
   if (message == null) {
     // Create and sign the message
-    message = Buffer.concat([
+    message = buildMessage(program.programId, Operation.Deposit, [
       snBuffer,
       accountBuffer,
       amount.toArrayLike(Buffer, 'le', 8),
@@ -254,8 +365,9 @@ export async function depositWithMessage(
       userToken: tokenAccount,
       programToken: programTokenPDA,
       mint: mint,
+      nativeUnwrap: null,
       record: recordPubkey,
-      tokenProgram: spl.TOKEN_PROGRAM_ID,
+      tokenProgram,
       systemProgram: SystemProgram.programId,
       associatedTokenProgram: spl.ASSOCIATED_TOKEN_PROGRAM_ID,
       instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -285,11 +397,11 @@ export async function depositWithMessage(
     assert.strictEqual(snBuffer.toString('hex'), depositEvent.sn.toString('hex'), "sn doesn't match");
     assert.strictEqual(payerKeypair.publicKey.toBase58(), depositEvent.user.toBase58(), "user doesn't match");
     
-    const programTokenAccountAfter = await getPDABalance(provider.connection, mint, programTokenPDA);
+    const programTokenAccountAfter = await getPDABalance(provider.connection, mint, programTokenPDA, tokenProgram);
     console.log("Program token account after deposit:", programTokenAccountAfter);
 
     // Log balances after deposit
-    const userTokenBalanceAfter = await getTokenAccountBalance(provider.connection, mint, payerKeypair.publicKey);
+    const userTokenBalanceAfter = await getTokenAccountBalance(provider.connection, mint, payerKeypair.publicKey, tokenProgram);
     console.log(
       "User Token Balance after deposit:",
       userTokenBalanceAfter
@@ -307,6 +419,7 @@ export async function depositWithMessage(
     return {
       userAccountPDA,
       userTokenAccountInfo,
+      depositEvent,
       userTokenBalanceAfter,
       programTokenAccountAfter,
     };
@@ -347,7 +460,10 @@ export async function withdraw(
   to: PublicKey,
   available: anchor.BN,
   frozen: anchor.BN,
-  expiredAt: anchor.BN
+  expiredAt: anchor.BN,
+  approvers: Keypair[] = [],
+  scheme: SignatureScheme = SignatureScheme.Ed25519,
+  tokenProgram: PublicKey = spl.TOKEN_PROGRAM_ID
 ) {
   const accountBuffer = bytes32Buffer(account);
   const snBuffer = bytes32Buffer(sn);
@@ -379,7 +495,7 @@ export async function withdraw(
   console.log('withdraw recordPubkey:', recordPubkey);
 
   // Create and sign the message
-  const message = Buffer.concat([
+  const message = buildMessage(program.programId, Operation.Withdraw, [
     snBuffer,
     accountBuffer,
     available.toArrayLike(Buffer, 'le', 8),
@@ -391,7 +507,9 @@ export async function withdraw(
   ]);
 
   try {
-    const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
+    const {instruction, signature} = getSignatureInstruction(message, signerKeypair, scheme);
+    // Approvals of the quorum signers, each in its own Ed25519 instruction
+    const approvals = approvers.map((approver) => getEd25519Instruction(message, approver).ed25519Instruction);
     // console.log('input parameters: ',  {user: payerKeypair.publicKey.toBase58(), token: mint, account:accountBuffer, available: available.toString(), frozen: frozen.toString(), sn: snBuffer, expiredAt, signature});
     const tx = await program.methods
       .withdraw(snBuffer, accountBuffer, available, frozen, expiredAt, signature)
@@ -403,13 +521,13 @@ export async function withdraw(
         to,
         programToken: programTokenPDA,
         record: recordPubkey,
-        tokenProgram: spl.TOKEN_PROGRAM_ID,
+        tokenProgram,
         systemProgram: SystemProgram.programId,
         associatedTokenProgram: spl.ASSOCIATED_TOKEN_PROGRAM_ID,
         instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
-      .preInstructions([ed25519Instruction])
+      .preInstructions([instruction, ...approvals])
       .signers([payerKeypair])
       .rpc();
 
@@ -455,7 +573,10 @@ export async function freeze(
   sn: string,
   account: string,
   amount: anchor.BN,
-  expiredAt: anchor.BN
+  expiredAt: anchor.BN,
+  holdId: Buffer = Buffer.alloc(32),
+  beneficiary: Buffer = Buffer.alloc(32),
+  releaseAt: anchor.BN = new anchor.BN(0)
 ) {
   const accountBuffer = bytes32Buffer(account);
   const snBuffer = bytes32Buffer(sn);
@@ -478,16 +599,18 @@ export async function freeze(
     program.programId
   );
 
-
   const userTokenAccountInfoBefore = await program.account.userTokenAccount.fetch(userAccountPDA);
   showUserTokenAccount(userTokenAccountInfoBefore, userAccountPDA, "User Account Info before freeze: ");
 
 
   // Create and sign the message
-  const message = Buffer.concat([
+  const message = buildMessage(program.programId, Operation.Freeze, [
     snBuffer,
     accountBuffer,
     amount.toArrayLike(Buffer, 'le', 8),
+    holdId,
+    beneficiary,
+    releaseAt.toArrayLike(Buffer, 'le', 8),
     expiredAt.toArrayLike(Buffer, 'le', 8),
     payerKeypair.publicKey.toBuffer(),
     mint.toBuffer(),
@@ -496,13 +619,14 @@ export async function freeze(
   try {
     const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
     const tx = await program.methods
-      .freeze(snBuffer, accountBuffer, amount, expiredAt, signature)
+      .freeze(snBuffer, accountBuffer, amount, holdId, beneficiary, releaseAt, expiredAt, signature)
       .accounts({
         paymentState: paymentStatePDA,
         userTokenAccount: userAccountPDA,
         user: payerKeypair.publicKey,
         mint: mint,
         record: recordPubkey,
+        hold: holdPDA(program, holdId),
         systemProgram: SystemProgram.programId,
         instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -565,6 +689,7 @@ export async function unfreezeWithAccount(
   expiredAt: anchor.BN,
   userAccountPDA: PublicKey | undefined,
   feeAccountPDA: PublicKey | undefined,
+  holdId: Buffer = Buffer.alloc(32),
 ) {
   const accountBuffer = bytes32Buffer(account);
   const snBuffer = bytes32Buffer(sn);
@@ -598,11 +723,12 @@ export async function unfreezeWithAccount(
   );
 
   // Create and sign the message
-  const message = Buffer.concat([
+  const message = buildMessage(program.programId, Operation.Unfreeze, [
     snBuffer,
     accountBuffer,
     amount.toArrayLike(Buffer, 'le', 8),
     fee.toArrayLike(Buffer, 'le', 8),
+    holdId,
     expiredAt.toArrayLike(Buffer, 'le', 8),
     payerKeypair.publicKey.toBuffer(),
     mint.toBuffer(),
@@ -611,7 +737,7 @@ export async function unfreezeWithAccount(
   try {
     const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
     const tx = await program.methods
-      .unfreeze(snBuffer, accountBuffer, amount, fee, expiredAt, signature)
+      .unfreeze(snBuffer, accountBuffer, amount, fee, holdId, expiredAt, signature)
       .accounts({
         paymentState: paymentStatePDA,
         userTokenAccount: userAccountPDA,
@@ -619,6 +745,7 @@ export async function unfreezeWithAccount(
         user: payerKeypair.publicKey,
         mint: mint,
         record: recordPubkey,
+        hold: holdPDA(program, holdId),
         systemProgram: SystemProgram.programId,
         instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -651,7 +778,8 @@ export async function unfreeze(
   account: string,
   amount: anchor.BN,
   fee: anchor.BN,
-  expiredAt: anchor.BN
+  expiredAt: anchor.BN,
+  holdId: Buffer = Buffer.alloc(32)
 ) {
   const accountBuffer = bytes32Buffer(account);
   const snBuffer = bytes32Buffer(sn);
@@ -694,6 +822,7 @@ export async function unfreeze(
       expiredAt,
       userAccountPDA,
       feeAccountPDA,
+      holdId,
     )
 
     console.log("Transaction signature:", tx);
@@ -811,7 +940,7 @@ export async function transferWitchAccount(
 
 
   // Create and sign the message
-  const message = Buffer.concat([
+  const message = buildMessage(program.programId, Operation.Transfer, [
     snBuffer,
     fromBuffer,
     toBuffer,
@@ -1088,7 +1217,7 @@ export async function settleWithAccount(
   }
 
   // Create and sign the message
-  const message = Buffer.concat([
+  const message = buildMessage(program.programId, Operation.Settle, [
     snBuffer,
     deal.toBytes(),
     expiredAt.toArrayLike(Buffer, 'le', 8),
@@ -1113,6 +1242,7 @@ export async function settleWithAccount(
         feeUser: feeUser,
         programToken: programTokenPDA,
         record: recordPubkey,
+        hold: holdPDA(program, deal.holdId),
         tokenProgram: spl.TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         associatedTokenProgram: spl.ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -1285,6 +1415,7 @@ export class SettlementData {
   fee: anchor.BN;
   paid: anchor.BN;
   excessFee: anchor.BN;
+  holdId: Buffer;
 
   constructor(
     from: Buffer,
@@ -1294,7 +1425,8 @@ export class SettlementData {
     amount: anchor.BN,
     fee: anchor.BN,
     paid: anchor.BN,
-    excessFee: anchor.BN
+    excessFee: anchor.BN,
+    holdId: Buffer = Buffer.alloc(32)
   ) {
     this.from = from;
     this.to = to;
@@ -1304,6 +1436,7 @@ export class SettlementData {
     this.fee = fee;
     this.paid = paid;
     this.excessFee = excessFee;
+    this.holdId = holdId;
   }
 
   toBytes(): Buffer {
@@ -1316,6 +1449,340 @@ export class SettlementData {
       this.fee.toArrayLike(Buffer, 'le', 8),
       this.paid.toArrayLike(Buffer, 'le', 8),
       this.excessFee.toArrayLike(Buffer, 'le', 8),
+      this.holdId,
+    ]);
+  }
+}
+
+export class SplitPayee {
+  account: Buffer;
+  out: PublicKey;
+  amount: anchor.BN;
+
+  constructor(account: Buffer, out: PublicKey, amount: anchor.BN) {
+    this.account = account;
+    this.out = out;
+    this.amount = amount;
+  }
+
+  toBytes(): Buffer {
+    return Buffer.concat([
+      this.account,
+      this.out.toBuffer(),
+      this.amount.toArrayLike(Buffer, 'le', 8),
+    ]);
+  }
+}
+
+export class SplitSettlementData {
+  from: Buffer;
+  available: anchor.BN;
+  frozen: anchor.BN;
+  fee: anchor.BN;
+  holdId: Buffer;
+  payees: SplitPayee[];
+
+  constructor(
+    from: Buffer,
+    available: anchor.BN,
+    frozen: anchor.BN,
+    fee: anchor.BN,
+    payees: SplitPayee[],
+    holdId: Buffer = Buffer.alloc(32)
+  ) {
+    this.from = from;
+    this.available = available;
+    this.frozen = frozen;
+    this.fee = fee;
+    this.holdId = holdId;
+    this.payees = payees;
+  }
+
+  toBytes(): Buffer {
+    return Buffer.concat([
+      this.from,
+      this.available.toArrayLike(Buffer, 'le', 8),
+      this.frozen.toArrayLike(Buffer, 'le', 8),
+      this.fee.toArrayLike(Buffer, 'le', 8),
+      this.holdId,
+      Buffer.from([this.payees.length]),
+      ...this.payees.map((payee) => payee.toBytes()),
     ]);
   }
-}
\ No newline at end of file
+}
+
+export async function settleSplit(
+  program: Program<Payment>,
+  payerKeypair: Keypair,
+  signerKeypair: Keypair,
+  mint: PublicKey,
+  sn: string,
+  deal: SplitSettlementData,
+  expiredAt: anchor.BN,
+  payeeAccounts: PublicKey[] | undefined = undefined
+) {
+  const snBuffer = bytes32Buffer(sn);
+  const [recordPubkey] = PublicKey.findProgramAddressSync(
+    [Buffer.from("record"), snBuffer],
+    program.programId
+  );
+
+  // Each payee is either credited on its ledger account or paid out to `out`
+  if (!payeeAccounts) {
+    payeeAccounts = deal.payees.map((payee) => payee.out.equals(ZERO_ACCOUNT)
+      ? PublicKey.findProgramAddressSync([Buffer.from("user-token"), payee.account, mint.toBuffer()], program.programId)[0]
+      : payee.out
+    );
+  }
+  const remainingAccounts = payeeAccounts.map((pubkey) => ({
+    pubkey,
+    isSigner: false,
+    isWritable: true,
+  }));
+
+  const message = buildMessage(program.programId, Operation.SettleSplit, [
+    snBuffer,
+    deal.toBytes(),
+    expiredAt.toArrayLike(Buffer, 'le', 8),
+    payerKeypair.publicKey.toBuffer(),
+    mint.toBuffer(),
+  ]);
+  const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
+  return await program.methods
+    .settleSplit(snBuffer, deal, expiredAt, signature)
+    .accounts({
+      user: payerKeypair.publicKey,
+      mint: mint,
+      record: recordPubkey,
+      hold: holdPDA(program, deal.holdId),
+      tokenProgram: spl.TOKEN_PROGRAM_ID,
+      instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    })
+    .remainingAccounts(remainingAccounts)
+    .preInstructions([ed25519Instruction])
+    .signers([payerKeypair])
+    .rpc();
+}
+
+export async function settleBatch(
+  program: Program<Payment>,
+  payerKeypair: Keypair,
+  signerKeypair: Keypair,
+  mint: PublicKey,
+  sn: string,
+  deals: SettlementData[],
+  expiredAt: anchor.BN,
+  ledgerAccounts: PublicKey[] | undefined = undefined
+) {
+  const snBuffer = bytes32Buffer(sn);
+  const [recordPubkey] = PublicKey.findProgramAddressSync(
+    [Buffer.from("record"), snBuffer],
+    program.programId
+  );
+
+  if (!ledgerAccounts) {
+    // Ledger accounts of every distinct from/to, in order of first appearance
+    const accounts: Buffer[] = [];
+    for (const deal of deals) {
+      for (const account of [deal.from, deal.to]) {
+        if (!accounts.some((a) => a.equals(account))) {
+          accounts.push(account);
+        }
+      }
+    }
+    ledgerAccounts = accounts.map((account) =>
+      PublicKey.findProgramAddressSync([Buffer.from("user-token"), account, mint.toBuffer()], program.programId)[0]
+    );
+  }
+  const remainingAccounts = ledgerAccounts.map((pubkey) => ({
+    pubkey,
+    isSigner: false,
+    isWritable: true,
+  }));
+
+  const count = Buffer.alloc(2);
+  count.writeUInt16LE(deals.length);
+  const batchHash = createHash('sha256').update(Buffer.concat(deals.map((deal) => deal.toBytes()))).digest();
+  const message = buildMessage(program.programId, Operation.SettleBatch, [
+    snBuffer,
+    count,
+    batchHash,
+    expiredAt.toArrayLike(Buffer, 'le', 8),
+    payerKeypair.publicKey.toBuffer(),
+    mint.toBuffer(),
+  ]);
+  const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
+  return await program.methods
+    .settleBatch(snBuffer, deals, expiredAt, signature)
+    .accounts({
+      user: payerKeypair.publicKey,
+      mint: mint,
+      record: recordPubkey,
+      instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    })
+    .remainingAccounts(remainingAccounts)
+    .preInstructions([ed25519Instruction])
+    .signers([payerKeypair])
+    .rpc();
+}
+
+export class TransferRecipient {
+  to: Buffer;
+  amount: anchor.BN;
+
+  constructor(to: Buffer, amount: anchor.BN) {
+    this.to = to;
+    this.amount = amount;
+  }
+
+  toBytes(): Buffer {
+    return Buffer.concat([this.to, this.amount.toArrayLike(Buffer, 'le', 8)]);
+  }
+}
+
+export async function transferMany(
+  program: Program<Payment>,
+  payerKeypair: Keypair,
+  signerKeypair: Keypair,
+  mint: PublicKey,
+  sn: string,
+  from: Buffer,
+  recipients: TransferRecipient[],
+  fee: anchor.BN,
+  expiredAt: anchor.BN
+) {
+  const snBuffer = bytes32Buffer(sn);
+  const [recordPubkey] = PublicKey.findProgramAddressSync(
+    [Buffer.from("record"), snBuffer],
+    program.programId
+  );
+  // Ledger account of every recipient, in the same order
+  const remainingAccounts = recipients.map((recipient) => ({
+    pubkey: PublicKey.findProgramAddressSync([Buffer.from("user-token"), recipient.to, mint.toBuffer()], program.programId)[0],
+    isSigner: false,
+    isWritable: true,
+  }));
+
+  const count = Buffer.alloc(2);
+  count.writeUInt16LE(recipients.length);
+  const recipientsHash = createHash('sha256').update(Buffer.concat(recipients.map((recipient) => recipient.toBytes()))).digest();
+  const message = buildMessage(program.programId, Operation.TransferMany, [
+    snBuffer,
+    from,
+    count,
+    recipientsHash,
+    fee.toArrayLike(Buffer, 'le', 8),
+    expiredAt.toArrayLike(Buffer, 'le', 8),
+    payerKeypair.publicKey.toBuffer(),
+    mint.toBuffer(),
+  ]);
+  const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
+  return await program.methods
+    .transferMany(snBuffer, from, recipients, fee, expiredAt, signature)
+    .accounts({
+      user: payerKeypair.publicKey,
+      mint: mint,
+      record: recordPubkey,
+      instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+    })
+    .remainingAccounts(remainingAccounts)
+    .preInstructions([ed25519Instruction])
+    .signers([payerKeypair])
+    .rpc();
+}
+
+// Creates a Token-2022 mint, enables it and sets up its vault and fee ledger.
+// Mints `supply` to the payer's associated token account. A non-zero
+// `transferFeeBasisPoints` adds the transfer fee extension with that rate.
+export async function setupToken2022Mint(
+  provider: anchor.AnchorProvider,
+  program: Program<Payment>,
+  payerKeypair: Keypair,
+  supply: bigint,
+  transferFeeBasisPoints: number = 0
+) {
+  const mintKeypair = Keypair.generate();
+  const mint = mintKeypair.publicKey;
+  if (transferFeeBasisPoints > 0) {
+    const mintLen = spl.getMintLen([spl.ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: payerKeypair.publicKey,
+        newAccountPubkey: mint,
+        space: mintLen,
+        lamports,
+        programId: spl.TOKEN_2022_PROGRAM_ID,
+      }),
+      spl.createInitializeTransferFeeConfigInstruction(
+        mint,
+        payerKeypair.publicKey,
+        payerKeypair.publicKey,
+        transferFeeBasisPoints,
+        BigInt(Number.MAX_SAFE_INTEGER),
+        spl.TOKEN_2022_PROGRAM_ID
+      ),
+      spl.createInitializeMintInstruction(mint, 9, payerKeypair.publicKey, null, spl.TOKEN_2022_PROGRAM_ID)
+    );
+    await sendAndConfirmTransaction(provider.connection, tx, [payerKeypair, mintKeypair]);
+  } else {
+    await spl.createMint(
+      provider.connection,
+      payerKeypair,
+      payerKeypair.publicKey,
+      null,
+      9,
+      mintKeypair,
+      undefined,
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+  }
+  const [paymentStatePDA] = PublicKey.findProgramAddressSync([Buffer.from("payment-state")], program.programId);
+  const paymentState = await program.account.paymentState.fetch(paymentStatePDA);
+
+  await program.methods
+    .setMintConfig({
+      enabled: true,
+      minDeposit: new anchor.BN(0),
+      maxDeposit: new anchor.BN(0),
+      maxBalance: new anchor.BN(0),
+      depositCap: new anchor.BN(0),
+    })
+    .accounts({
+      paymentState: paymentStatePDA,
+      owner: payerKeypair.publicKey,
+      mint,
+      mintConfig: PublicKey.findProgramAddressSync([Buffer.from("mint-config"), mint.toBuffer()], program.programId)[0],
+      tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([payerKeypair])
+    .rpc();
+
+  const [programTokenPDA] = PublicKey.findProgramAddressSync([Buffer.from("program-token"), mint.toBuffer()], program.programId);
+  await program.methods
+    .initializeProgramToken()
+    .accounts({
+      paymentState: paymentStatePDA,
+      owner: payerKeypair.publicKey,
+      mint,
+      programToken: programTokenPDA,
+      feeTokenAccount: PublicKey.findProgramAddressSync(
+        [Buffer.from("user-token"), Buffer.from(paymentState.feeToAccount), mint.toBuffer()],
+        program.programId
+      )[0],
+      tokenProgram: spl.TOKEN_2022_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    })
+    .signers([payerKeypair])
+    .rpc();
+
+  const payerToken = await spl.getOrCreateAssociatedTokenAccount(
+    provider.connection, payerKeypair, mint, payerKeypair.publicKey, false, undefined, undefined, spl.TOKEN_2022_PROGRAM_ID
+  );
+  await spl.mintTo(
+    provider.connection, payerKeypair, mint, payerToken.address, payerKeypair, supply, [], undefined, spl.TOKEN_2022_PROGRAM_ID
+  );
+  return { mint, programTokenPDA, payerToken: payerToken.address };
+}
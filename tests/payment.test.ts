@@ -30,8 +30,27 @@ import {
   unfreeze,
   unfreezeWithAccount,
   transfer,
+  transferWitchAccount,
   settle,
+  settleWithAccount,
   SettlementData,
+  SplitPayee,
+  SplitSettlementData,
+  settleSplit,
+  settleBatch,
+  TransferRecipient,
+  transferMany,
+  Operation,
+  ReplayMode,
+  buildMessage,
+  getEd25519Instruction,
+  getSecp256k1Instruction,
+  ethAddressOf,
+  setupToken2022Mint,
+  SignatureScheme,
+  getChainTime,
+  waitForChainTime,
+  expectError,
   checkTransactionExecuted 
 } from "./common";
 import { deployToken, getTokenInfo, getTokenAccountBalance, getPDABalance } from "../scripts/tokens";
@@ -277,6 +296,10 @@ describe("payment", () => {
       assert.strictEqual(programTokenAccount.owner.toBase58(), paymentStatePDA.toBase58(), "Program token account owner doesn't match");
       assert.strictEqual(programTokenAccount.amount.toString(), "0", "Program token account should have 0 balance initially");
 
+      const [tokenTotalsPDA] = PublicKey.findProgramAddressSync([Buffer.from("mint-totals"), mint.toBuffer()], program.programId);
+      const tokenTotals = await program.account.mintTotals.fetch(tokenTotalsPDA);
+      assert.strictEqual(tokenTotals.seeded, true, "Totals of a new vault start seeded");
+
       const feeTokenAccountInfo = await program.account.userTokenAccount.fetch(feeTokenPDA);
       showUserTokenAccount(feeTokenAccountInfo, feeTokenPDA, "Fee Account Info token: ");
       assert.strictEqual(feeTokenAccountInfo.available.toNumber(), 0, "feeTokenAccountInfo available doesn't match");
@@ -287,6 +310,18 @@ describe("payment", () => {
       throw error;
     }
 
+    // The SOL vault already holds the deposits of the legacy ledger account loaded from
+    // tests/fixtures, as on a deployment from before the totals were tracked. The deposit
+    // of the unbacked ledger account never reached it, see "SOL payouts keep the vault rent-exempt"
+    const vaultRent = await provider.connection.getMinimumBalanceForRentExemption(0);
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: payerKeypair.publicKey,
+        toPubkey: programSolPDA,
+        lamports: vaultRent + 5000 + 700,
+      })
+    ), [payerKeypair]);
+
     accountInfo = await provider.connection.getAccountInfo(programSolPDA);
     console.log("programSolPDA accountInfo:", accountInfo);
     // Initialize SOL token
@@ -314,6 +349,10 @@ describe("payment", () => {
       assert(programSolPDAInfo.owner.equals(SystemProgram.programId), "Program SOL account should be owned by the System Program");
       assert(programSolPDAInfo.lamports > 0, "Program SOL account should have some balance");
       
+      const [solTotalsPDA] = PublicKey.findProgramAddressSync([Buffer.from("mint-totals"), spl.NATIVE_MINT.toBuffer()], program.programId);
+      const solTotals = await program.account.mintTotals.fetch(solTotalsPDA);
+      assert.strictEqual(solTotals.seeded, false, "Totals of a vault holding deposits need seeding");
+
       const feeSolAccountInfo = await program.account.userTokenAccount.fetch(feeSolPDA);
       showUserTokenAccount(feeSolAccountInfo, feeSolPDA, "Fee Account Info sol: ");
       assert.strictEqual(feeSolAccountInfo.available.toNumber(), 0, "feeSolAccountInfo available doesn't match");
@@ -337,6 +376,142 @@ describe("payment", () => {
     assert(programSolPDA.equals(expectedProgramSolAccount), "SOL Program token address is not correctly derived");
   });
 
+  it("Set mint config", async () => {
+    for (const configMint of [mint, spl.NATIVE_MINT]) {
+      const [mintConfigPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-config"), configMint.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .setMintConfig({
+          enabled: true,
+          minDeposit: new anchor.BN(0),
+          maxDeposit: new anchor.BN(0),
+          maxBalance: new anchor.BN(0),
+          depositCap: new anchor.BN(0),
+        })
+        .accounts({
+          paymentState: paymentStatePDA,
+          owner: payerKeypair.publicKey,
+          mint: configMint,
+          mintConfig: mintConfigPDA,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([payerKeypair])
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(mintConfigPDA);
+      assert.strictEqual(mintConfig.enabled, true, "Mint config should be enabled");
+      assert.strictEqual(mintConfig.mint.toBase58(), configMint.toBase58(), "Mint config mint doesn't match");
+    }
+  });
+
+  it("Seed SOL totals of a vault holding deposits", async () => {
+    const [mintTotalsPDA] = PublicKey.findProgramAddressSync([Buffer.from("mint-totals"), spl.NATIVE_MINT.toBuffer()], program.programId);
+    const [mintConfigPDA] = PublicKey.findProgramAddressSync([Buffer.from("mint-config"), spl.NATIVE_MINT.toBuffer()], program.programId);
+    const seedAccounts = {
+      paymentState: paymentStatePDA,
+      owner: payerKeypair.publicKey,
+      mint: spl.NATIVE_MINT,
+      mintConfig: mintConfigPDA,
+      mintTotals: mintTotalsPDA,
+      systemProgram: SystemProgram.programId,
+    };
+
+    // The mint stays unusable until its totals match the ledger
+    try {
+      await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), uuid(), new anchor.BN(LAMPORTS_PER_SOL / 100), new anchor.BN(0), expiredAt);
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "TotalsNotSeeded");
+    }
+
+    try {
+      await program.methods
+        .seedMintTotals(new anchor.BN(5000), new anchor.BN(700), new anchor.BN(0))
+        .accounts({ ...seedAccounts, owner: user2Keypair.publicKey })
+        .signers([user2Keypair])
+        .rpc();
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "Unauthorized");
+    }
+
+    // Unseeded totals would count the deposits in the vault as surplus
+    try {
+      await program.methods
+        .sweepSurplus(new anchor.BN(5700))
+        .accounts({
+          owner: payerKeypair.publicKey,
+          mint: spl.NATIVE_MINT,
+          to: user2Keypair.publicKey,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+        })
+        .signers([payerKeypair])
+        .rpc();
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "TotalsNotSeeded");
+    }
+
+    // Sums of the legacy and the unbacked ledger accounts, the only SOL ledgers so far
+    await program.methods
+      .seedMintTotals(new anchor.BN(5000 + 6000), new anchor.BN(700), new anchor.BN(0))
+      .accounts(seedAccounts)
+      .signers([payerKeypair])
+      .rpc();
+    const totals = await program.account.mintTotals.fetch(mintTotalsPDA);
+    assert.strictEqual(totals.seeded, true);
+    assert.strictEqual(totals.totalAvailable.toNumber(), 11000);
+    assert.strictEqual(totals.totalFrozen.toNumber(), 700);
+    const mintConfig = await program.account.mintConfig.fetch(mintConfigPDA);
+    assert.strictEqual(mintConfig.totalDeposits.toNumber(), 11700);
+
+    // Seeding is one-time
+    try {
+      await program.methods
+        .seedMintTotals(new anchor.BN(0), new anchor.BN(0), new anchor.BN(0))
+        .accounts(seedAccounts)
+        .signers([payerKeypair])
+        .rpc();
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "TotalsAlreadySeeded");
+    }
+  });
+
+  it("SOL payouts keep the vault rent-exempt", async () => {
+    // Loaded by the test validator with 6000 lamports the vault does not hold, see Anchor.toml
+    const account = '7e57';
+    const amount = new anchor.BN(6000);
+    const zero = new anchor.BN(0);
+    const vaultRent = await provider.connection.getMinimumBalanceForRentExemption(0);
+    const vaultBalance = await provider.connection.getBalance(programSolPDA);
+    assert(vaultBalance - vaultRent < amount.toNumber(), "The payout should exceed what the vault holds above its reserve");
+
+    await expectError(
+      withdraw(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), account, payerKeypair.publicKey, amount, zero, expiredAt),
+      "VaultRentReserve"
+    );
+    assert.strictEqual(await provider.connection.getBalance(programSolPDA), vaultBalance, "The vault should be left untouched");
+
+    // Once the missing deposit is made good the payout goes through
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: payerKeypair.publicKey,
+        toPubkey: programSolPDA,
+        lamports: amount.toNumber(),
+      })
+    ), [payerKeypair]);
+    await withdraw(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), account, payerKeypair.publicKey, amount, zero, expiredAt);
+    assert.strictEqual(await provider.connection.getBalance(programSolPDA), vaultBalance, "Only the made good deposit should leave the vault");
+  });
+
   it("Change fee to", async () => {
     try {
       const tx = await program.methods
@@ -349,6 +524,19 @@ describe("payment", () => {
         .signers([payerKeypair])
         .rpc();
 
+      await program.methods
+        .acceptFeeTo()
+        .accounts({
+          paymentState: paymentStatePDA,
+          newOwner: feeToKeypair.publicKey,
+        })
+        .signers([feeToKeypair])
+        .rpc();
+
+      const paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+      assert.strictEqual(paymentStateAccount.feeTo.toBase58(), feeToKeypair.publicKey.toBase58(), "fee_to doesn't match");
+      assert.strictEqual(paymentStateAccount.pendingFeeTo.toBase58(), ZERO_ACCOUNT.toBase58(), "pending fee_to should be cleared");
+
     } catch (error) {
       console.error("Error change fee to:", error);
       throw error;
@@ -367,12 +555,305 @@ describe("payment", () => {
         .signers([payerKeypair])
         .rpc();
 
+      await program.methods
+        .acceptSigner()
+        .accounts({
+          paymentState: paymentStatePDA,
+          newOwner: signerKeypair.publicKey,
+        })
+        .signers([signerKeypair])
+        .rpc();
+
+      const paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+      assert.strictEqual(paymentStateAccount.signer.toBase58(), signerKeypair.publicKey.toBase58(), "signer doesn't match");
+      assert.strictEqual(paymentStateAccount.pendingSigner.toBase58(), ZERO_ACCOUNT.toBase58(), "pending signer should be cleared");
+
     } catch (error) {
       console.error("Error change signer:", error);
       throw error;
     }
   });
 
+  it("Previous signer is accepted only during the overlap", async () => {
+    const config = { paymentState: paymentStatePDA, owner: payerKeypair.publicKey };
+    const rotatedKeypair = Keypair.generate();
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const zero = new anchor.BN(0);
+
+    try {
+      await program.methods
+        .setSignerOverlap(new anchor.BN(7 * 24 * 60 * 60 + 1))
+        .accounts(config)
+        .signers([payerKeypair])
+        .rpc();
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "InvalidParameter");
+    }
+
+    await program.methods.setSignerOverlap(new anchor.BN(3)).accounts(config).signers([payerKeypair]).rpc();
+    await program.methods
+      .changeSigner()
+      .accounts({ paymentState: paymentStatePDA, currentOwner: payerKeypair.publicKey, newOwner: rotatedKeypair.publicKey })
+      .signers([payerKeypair])
+      .rpc();
+    await program.methods
+      .acceptSigner()
+      .accounts({ paymentState: paymentStatePDA, newOwner: rotatedKeypair.publicKey })
+      .signers([rotatedKeypair])
+      .rpc();
+
+    let paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.previousSigner.toBase58(), signerKeypair.publicKey.toBase58());
+
+    // Inside the window both the new and the previous signer are accepted
+    await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), uuid(), amount, zero, expiredAt);
+    await depositSol(provider, program, payerKeypair, rotatedKeypair, uuid(), uuid(), amount, zero, expiredAt);
+
+    await waitForChainTime(provider, paymentStateAccount.previousValidUntil.toNumber());
+    try {
+      await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), uuid(), amount, zero, expiredAt);
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "InvalidPublicKey");
+    }
+
+    // Restore the original signer for the rest of the suite
+    await program.methods.setSignerOverlap(new anchor.BN(0)).accounts(config).signers([payerKeypair]).rpc();
+    await program.methods
+      .changeSigner()
+      .accounts({ paymentState: paymentStatePDA, currentOwner: payerKeypair.publicKey, newOwner: signerKeypair.publicKey })
+      .signers([payerKeypair])
+      .rpc();
+    await program.methods
+      .acceptSigner()
+      .accounts({ paymentState: paymentStatePDA, newOwner: signerKeypair.publicKey })
+      .signers([signerKeypair])
+      .rpc();
+    paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.signer.toBase58(), signerKeypair.publicKey.toBase58());
+  });
+
+  it("Toggle nosn mode", async () => {
+    await program.methods
+      .setNosnEnabled(true)
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+      })
+      .signers([payerKeypair])
+      .rpc();
+
+    let paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.nosnEnabled, true, "nosn mode should be enabled");
+
+    await program.methods
+      .setNosnEnabled(false)
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+      })
+      .signers([payerKeypair])
+      .rpc();
+
+    paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.nosnEnabled, false, "nosn mode should be disabled");
+  });
+
+  it("Replay across record and nonce modes", async () => {
+    const account = uuid();
+    const accountBuffer = bytes32Buffer(account);
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 100);
+    await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), account, amount, new anchor.BN(0), expiredAt);
+    const [userAccountPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), accountBuffer, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+
+    // nosn sn: the first 8 bytes carry the nonce
+    const nonceSn = (nonce: number) => {
+      const sn = Buffer.concat([Buffer.alloc(8), bytes32Buffer(uuid()).subarray(8)]);
+      sn.writeBigUInt64LE(BigInt(nonce));
+      return sn;
+    };
+    const signFreeze = (sn: Buffer, replayMode: ReplayMode) => getEd25519Instruction(buildMessage(program.programId, Operation.Freeze, [
+      sn,
+      accountBuffer,
+      amount.divn(4).toArrayLike(Buffer, 'le', 8),
+      Buffer.alloc(32),
+      Buffer.alloc(32),
+      new anchor.BN(0).toArrayLike(Buffer, 'le', 8),
+      expiredAt.toArrayLike(Buffer, 'le', 8),
+      payerKeypair.publicKey.toBuffer(),
+      spl.NATIVE_MINT.toBuffer(),
+    ], replayMode), signerKeypair);
+    // Submits the same signed freeze, with or without the record account of its sn
+    const submitFreeze = (sn: Buffer, signed: ReturnType<typeof signFreeze>, withRecord: boolean) => program.methods
+      .freeze(sn, accountBuffer, amount.divn(4), Buffer.alloc(32), Buffer.alloc(32), new anchor.BN(0), expiredAt, signed.signature)
+      .accounts({
+        paymentState: paymentStatePDA,
+        userTokenAccount: userAccountPDA,
+        user: payerKeypair.publicKey,
+        mint: spl.NATIVE_MINT,
+        record: withRecord ? PublicKey.findProgramAddressSync([Buffer.from("record"), sn], program.programId)[0] : null,
+        hold: null,
+        systemProgram: SystemProgram.programId,
+        instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .preInstructions([signed.ed25519Instruction])
+      .signers([payerKeypair])
+      .rpc();
+    await program.methods
+      .setNosnEnabled(true)
+      .accounts({ paymentState: paymentStatePDA, owner: payerKeypair.publicKey })
+      .signers([payerKeypair])
+      .rpc();
+
+    // Executed through the nonce, then replayed with a fresh record account
+    const sn1 = nonceSn(1);
+    const signed1 = signFreeze(sn1, ReplayMode.Nonce);
+    await submitFreeze(sn1, signed1, false);
+    await expectError(submitFreeze(sn1, signed1, true), "InvalidMessage");
+    await expectError(submitFreeze(sn1, signed1, false), "InvalidNonce");
+
+    // Executed through the record, then replayed without it and a nonce above the stored one
+    const sn2 = nonceSn(2);
+    const signed2 = signFreeze(sn2, ReplayMode.Record);
+    await submitFreeze(sn2, signed2, true);
+    await expectError(submitFreeze(sn2, signed2, false), "InvalidMessage");
+    await expectError(submitFreeze(sn2, signed2, true), "AlreadyExecuted");
+
+    const userAccount = await program.account.userTokenAccount.fetch(userAccountPDA);
+    assert.strictEqual(userAccount.frozen.toString(), amount.divn(2).toString(), "Only the two original freezes should apply");
+    assert.strictEqual(userAccount.nonce.toNumber(), 1);
+
+    await program.methods
+      .setNosnEnabled(false)
+      .accounts({ paymentState: paymentStatePDA, owner: payerKeypair.publicKey })
+      .signers([payerKeypair])
+      .rpc();
+  });
+
+  it("Migrate legacy user token account", async () => {
+    // Loaded by the test validator in the layout from before nonce, wallet and holds, see Anchor.toml
+    const legacyAccount = bytes32Buffer('1e9a');
+    const [legacyPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), legacyAccount, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const sizeBefore = (await provider.connection.getAccountInfo(legacyPDA)).data.length;
+    assert.strictEqual(sizeBefore, 8 + 32 + 8 + 8, "Fixture should have the legacy layout");
+    let loaded = true;
+    try {
+      await program.account.userTokenAccount.fetch(legacyPDA);
+    } catch (error) {
+      loaded = false;
+    }
+    assert.strictEqual(loaded, false, "The legacy layout should not deserialize");
+
+    // Permissionless, anyone may pay for the extra rent
+    await program.methods
+      .migrateUserTokenAccount(legacyAccount)
+      .accounts({
+        userTokenAccount: legacyPDA,
+        mint: spl.NATIVE_MINT,
+        payer: user2Keypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user2Keypair])
+      .rpc();
+
+    const sizeAfter = (await provider.connection.getAccountInfo(legacyPDA)).data.length;
+    assert.strictEqual(sizeAfter, 8 + 32 + 8 * 3 + 32 + 8 * 2);
+    const legacy = await program.account.userTokenAccount.fetch(legacyPDA);
+    assert.strictEqual(legacy.mint.toBase58(), spl.NATIVE_MINT.toBase58());
+    assert.strictEqual(legacy.available.toNumber(), 5000);
+    assert.strictEqual(legacy.frozen.toNumber(), 700);
+    assert.strictEqual(legacy.nonce.toNumber(), 0);
+    assert.strictEqual(legacy.held.toNumber(), 0);
+    assert.strictEqual(legacy.wallet.toBase58(), PublicKey.default.toBase58());
+  });
+
+  it("Pause and unpause withdraw", async () => {
+    const PAUSE_WITHDRAW = 1 << 1;
+    await program.methods
+      .setPaused(PAUSE_WITHDRAW)
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+      })
+      .signers([payerKeypair])
+      .rpc();
+
+    let paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.paused, PAUSE_WITHDRAW, "Withdraw should be paused");
+    assert.strictEqual(paymentStateAccount.enabled, true, "Payment state should stay enabled");
+
+    try {
+      await program.methods
+        .setPaused(0)
+        .accounts({
+          paymentState: paymentStatePDA,
+          owner: user2Keypair.publicKey,
+        })
+        .signers([user2Keypair])
+        .rpc();
+      assert.fail("Expected an Unauthorized error");
+    } catch(e:any) {
+      assert.strictEqual(e.error.errorCode.code, "Unauthorized");
+    }
+
+    await program.methods
+      .setPaused(0)
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+      })
+      .signers([payerKeypair])
+      .rpc();
+
+    paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.paused, 0, "Withdraw should be unpaused");
+  });
+
+  it("Migrate payment state", async () => {
+    const sizeBefore = (await provider.connection.getAccountInfo(paymentStatePDA)).data.length;
+
+    try {
+      await program.methods
+        .migratePaymentState()
+        .accounts({
+          paymentState: paymentStatePDA,
+          owner: user2Keypair.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2Keypair])
+        .rpc();
+      assert.fail("Expected an Unauthorized error");
+    } catch(e:any) {
+      assert.strictEqual(e.error.errorCode.code, "Unauthorized");
+    }
+
+    // Already at the current layout, so nothing changes
+    await program.methods
+      .migratePaymentState()
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payerKeypair])
+      .rpc();
+
+    const sizeAfter = (await provider.connection.getAccountInfo(paymentStatePDA)).data.length;
+    assert.strictEqual(sizeAfter, sizeBefore, "Payment state size should not change");
+    const paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.owner.toBase58(), payerKeypair.publicKey.toBase58());
+  });
+
   it("Fails to deposit SOL with incorrect signature", async () => {
     const amount = new anchor.BN(LAMPORTS_PER_SOL); // 1 SOL
     const frozen = new anchor.BN(LAMPORTS_PER_SOL / 10); // 0.1 SOL
@@ -413,7 +894,7 @@ describe("payment", () => {
     }
 
     //test InvalidPublicKey
-    message = Buffer.concat([
+    message = buildMessage(program.programId, Operation.Deposit, [
       bytes32Buffer(sn),
       bytes32Buffer(account),
       amount.toArrayLike(Buffer, 'le', 8),
@@ -517,6 +998,92 @@ describe("payment", () => {
     }
   });
 
+  it("Deposits from and withdraws to a wSOL account", async () => {
+    const account = uuid();
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 100);
+    const zero = new anchor.BN(0);
+    const [userAccountPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), bytes32Buffer(account), spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const [nativeUnwrapPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("native-unwrap"), payerKeypair.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // Wrap twice the deposit into the payer's wSOL account
+    const wsolAccount = (await spl.getOrCreateAssociatedTokenAccount(
+      provider.connection, payerKeypair, spl.NATIVE_MINT, payerKeypair.publicKey
+    )).address;
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: payerKeypair.publicKey,
+        toPubkey: wsolAccount,
+        lamports: amount.muln(2).toNumber(),
+      }),
+      spl.createSyncNativeInstruction(wsolAccount)
+    ), [payerKeypair]);
+    const wsolBefore = (await spl.getAccount(provider.connection, wsolAccount)).amount;
+
+    const depositFromWsol = (sn: string, nativeUnwrap: PublicKey | null) => {
+      const snBuffer = bytes32Buffer(sn);
+      const message = buildMessage(program.programId, Operation.Deposit, [
+        snBuffer,
+        bytes32Buffer(account),
+        amount.toArrayLike(Buffer, 'le', 8),
+        zero.toArrayLike(Buffer, 'le', 8),
+        expiredAt.toArrayLike(Buffer, 'le', 8),
+        payerKeypair.publicKey.toBuffer(),
+        spl.NATIVE_MINT.toBuffer(),
+        wsolAccount.toBuffer(),
+      ]);
+      const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
+      return program.methods
+        .deposit(snBuffer, bytes32Buffer(account), amount, zero, expiredAt, signature)
+        .accounts({
+          paymentState: paymentStatePDA,
+          userTokenAccount: userAccountPDA,
+          user: payerKeypair.publicKey,
+          userToken: wsolAccount,
+          programToken: programSolPDA,
+          mint: spl.NATIVE_MINT,
+          nativeUnwrap,
+          record: PublicKey.findProgramAddressSync([Buffer.from("record"), snBuffer], program.programId)[0],
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          associatedTokenProgram: spl.ASSOCIATED_TOKEN_PROGRAM_ID,
+          instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .preInstructions([ed25519Instruction])
+        .signers([payerKeypair])
+        .rpc();
+    };
+
+    // Unwrapping needs the scratch account
+    try {
+      await depositFromWsol(uuid(), null);
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "MissingNativeUnwrap");
+    }
+
+    const vaultBefore = await provider.connection.getBalance(programSolPDA);
+    await depositFromWsol(uuid(), nativeUnwrapPDA);
+    assert.strictEqual((await spl.getAccount(provider.connection, wsolAccount)).amount, wsolBefore - BigInt(amount.toString()));
+    assert.strictEqual(await provider.connection.getBalance(programSolPDA), vaultBefore + amount.toNumber());
+    assert.strictEqual(await provider.connection.getAccountInfo(nativeUnwrapPDA), null, "The scratch account should be closed");
+    let userTokenAccount = await program.account.userTokenAccount.fetch(userAccountPDA);
+    assert.strictEqual(userTokenAccount.available.toString(), amount.toString());
+
+    // Withdrawing to a wSOL account wraps the lamports again
+    await withdraw(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), account, wsolAccount, amount, zero, expiredAt);
+    assert.strictEqual((await spl.getAccount(provider.connection, wsolAccount)).amount, wsolBefore);
+    userTokenAccount = await program.account.userTokenAccount.fetch(userAccountPDA);
+    assert.strictEqual(userTokenAccount.available.toString(), "0");
+  });
+
   it("Deposits tokens", async () => {
     const amount = new anchor.BN(1000000000); // 1 tokens
     const frozen = new anchor.BN(100000000); // 0.1 tokens
@@ -676,6 +1243,128 @@ describe("payment", () => {
 
   });
 
+  it("Withdraw needs the quorum of signers", async () => {
+    const account = uuid();
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 100);
+    const part = amount.divn(4);
+    const zero = new anchor.BN(0);
+    await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), account, amount, zero, expiredAt);
+    const approver1 = Keypair.generate();
+    const approver2 = Keypair.generate();
+    const config = { paymentState: paymentStatePDA, owner: payerKeypair.publicKey };
+    await program.methods.addQuorumSigner(approver1.publicKey).accounts(config).signers([payerKeypair]).rpc();
+    await expectError(
+      program.methods.addQuorumSigner(approver1.publicKey).accounts(config).signers([payerKeypair]).rpc(),
+      "SignerAlreadyExists"
+    );
+    await program.methods.addQuorumSigner(approver2.publicKey).accounts(config).signers([payerKeypair]).rpc();
+    await program.methods.setQuorumThreshold(2).accounts(config).signers([payerKeypair]).rpc();
+    await expectError(
+      program.methods.setQuorumThreshold(3).accounts(config).signers([payerKeypair]).rpc(),
+      "InvalidThreshold"
+    );
+
+    // One signer left could never reach a threshold of 2
+    await expectError(
+      program.methods.removeQuorumSigner(approver2.publicKey).accounts(config).signers([payerKeypair]).rpc(),
+      "InvalidThreshold"
+    );
+
+    // M-1 approvals are not enough
+    await expectError(
+      withdraw(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), account, payerKeypair.publicKey, part, zero, expiredAt, [approver1]),
+      "InsufficientApprovals"
+    );
+    await withdraw(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), account, payerKeypair.publicKey, part, zero, expiredAt, [approver1, approver2]);
+
+    // A threshold of 0 turns the quorum off, the backend signature alone is enough again
+    await program.methods.setQuorumThreshold(0).accounts(config).signers([payerKeypair]).rpc();
+    await withdraw(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), account, payerKeypair.publicKey, part, zero, expiredAt);
+
+    await program.methods.removeQuorumSigner(approver1.publicKey).accounts(config).signers([payerKeypair]).rpc();
+    await program.methods.removeQuorumSigner(approver2.publicKey).accounts(config).signers([payerKeypair]).rpc();
+    const paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.quorumSigners.length, 0);
+    assert.strictEqual(paymentStateAccount.quorumThreshold, 0);
+  });
+
+  it("Withdraw signed by the Ethereum signer", async () => {
+    const config = { paymentState: paymentStatePDA, owner: payerKeypair.publicKey };
+    const ethSignerKeypair = Keypair.generate();
+    const ethAddress = ethAddressOf(ethSignerKeypair);
+    const account = uuid();
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 100);
+    const zero = new anchor.BN(0);
+    await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), account, amount, zero, expiredAt);
+    const acceptEthSigner = (keypair: Keypair) => {
+      const message = buildMessage(program.programId, Operation.AcceptEthSigner, [ethAddress]);
+      const {secp256k1Instruction, signature} = getSecp256k1Instruction(message, keypair);
+      return program.methods
+        .acceptEthSigner(Array.from(signature))
+        .accounts({ paymentState: paymentStatePDA, instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY })
+        .preInstructions([secp256k1Instruction])
+        .rpc();
+    };
+
+    await expectError(
+      program.methods.setSignatureScheme(2).accounts(config).signers([payerKeypair]).rpc(),
+      "InvalidSignatureScheme"
+    );
+    // No Ethereum signer has been accepted yet
+    await expectError(
+      program.methods.setSignatureScheme(SignatureScheme.Secp256k1).accounts(config).signers([payerKeypair]).rpc(),
+      "InvalidParameter"
+    );
+
+    await program.methods.changeEthSigner(Array.from(ethAddress)).accounts(config).signers([payerKeypair]).rpc();
+    let paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.deepStrictEqual(Buffer.from(paymentStateAccount.pendingEthSigner), ethAddress);
+    assert.deepStrictEqual(Buffer.from(paymentStateAccount.ethSigner), Buffer.alloc(20));
+
+    // Only the key of the pending address can accept
+    await expectError(acceptEthSigner(Keypair.generate()), "InvalidPublicKey");
+    await acceptEthSigner(ethSignerKeypair);
+    paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.deepStrictEqual(Buffer.from(paymentStateAccount.ethSigner), ethAddress);
+    assert.deepStrictEqual(Buffer.from(paymentStateAccount.pendingEthSigner), Buffer.alloc(20));
+
+    // Quorum approvals are Ed25519 signatures, so the quorum blocks the switch
+    const approver = Keypair.generate();
+    await program.methods.addQuorumSigner(approver.publicKey).accounts(config).signers([payerKeypair]).rpc();
+    await program.methods.setQuorumThreshold(1).accounts(config).signers([payerKeypair]).rpc();
+    await expectError(
+      program.methods.setSignatureScheme(SignatureScheme.Secp256k1).accounts(config).signers([payerKeypair]).rpc(),
+      "QuorumRequiresEd25519"
+    );
+    await program.methods.setQuorumThreshold(0).accounts(config).signers([payerKeypair]).rpc();
+    await program.methods.removeQuorumSigner(approver.publicKey).accounts(config).signers([payerKeypair]).rpc();
+
+    await program.methods.setSignatureScheme(SignatureScheme.Secp256k1).accounts(config).signers([payerKeypair]).rpc();
+    paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.strictEqual(paymentStateAccount.signatureScheme, SignatureScheme.Secp256k1);
+    try {
+      await expectError(
+        withdraw(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), account, payerKeypair.publicKey, amount, zero, expiredAt),
+        "MissingSecp256k1Instruction"
+      );
+      // Nor can a quorum be turned on while the scheme is secp256k1
+      await program.methods.addQuorumSigner(approver.publicKey).accounts(config).signers([payerKeypair]).rpc();
+      await expectError(
+        program.methods.setQuorumThreshold(1).accounts(config).signers([payerKeypair]).rpc(),
+        "QuorumRequiresEd25519"
+      );
+      await program.methods.removeQuorumSigner(approver.publicKey).accounts(config).signers([payerKeypair]).rpc();
+
+      await withdraw(provider, program, payerKeypair, ethSignerKeypair, spl.NATIVE_MINT, uuid(), account, payerKeypair.publicKey, amount, zero, expiredAt, [], SignatureScheme.Secp256k1);
+      const userTokenAccount = await program.account.userTokenAccount.fetch(
+        PublicKey.findProgramAddressSync([Buffer.from("user-token"), bytes32Buffer(account), spl.NATIVE_MINT.toBuffer()], program.programId)[0]
+      );
+      assert.strictEqual(userTokenAccount.available.toString(), "0");
+    } finally {
+      await program.methods.setSignatureScheme(SignatureScheme.Ed25519).accounts(config).signers([payerKeypair]).rpc();
+    }
+  });
+
   it("Deposits and then withdraws Tokens", async () => {
     const account = String(TOKEN_DEPOSIT_ACCOUNT_FILL);
     const depositAmount = new anchor.BN(2000000000);
@@ -855,18 +1544,142 @@ describe("payment", () => {
     }
   });
 
-  it("Freeze and Unfreeze for SOL", async () => {
-    const amount = new anchor.BN(LAMPORTS_PER_SOL / 10); // 0.1 SOL
-    const fee = amount.div(new anchor.BN(10)); // 0.01 SOL
-    const account = String(SOL_DEPOSIT_ACCOUNT_FILL);
-    // Record balances before freeze
-    const programBalanceBefore = await provider.connection.getBalance(programSolPDA);
-    console.log("Program SOL Balance before freeze:", programBalanceBefore / LAMPORTS_PER_SOL);
-    let sn = uuid();
-    try {
-      await freeze(
-        provider,
-        program,
+  it("Deposits and then withdraws Token-2022 tokens", async () => {
+    const { mint: mint2022, programTokenPDA: program2022PDA, payerToken } =
+      await setupToken2022Mint(provider, program, payerKeypair, BigInt(10_000_000));
+    const account = uuid();
+    const amount = new anchor.BN(1_000_000);
+    const zero = new anchor.BN(0);
+
+    const { userAccountPDA, userTokenAccountInfo } = await depositWithMessage(
+      provider, program, payerKeypair, signerKeypair, mint2022, uuid(), account, amount, zero, expiredAt, null, spl.TOKEN_2022_PROGRAM_ID
+    );
+    assert.strictEqual(userTokenAccountInfo.available.toString(), amount.toString());
+    assert.strictEqual((await getPDABalance(provider.connection, mint2022, program2022PDA, spl.TOKEN_2022_PROGRAM_ID)).toString(), amount.toString());
+
+    const part = amount.divn(4);
+    await withdraw(
+      provider, program, payerKeypair, signerKeypair, mint2022, uuid(), account, payerToken, part, zero, expiredAt,
+      [], SignatureScheme.Ed25519, spl.TOKEN_2022_PROGRAM_ID
+    );
+    const userTokenAccount = await program.account.userTokenAccount.fetch(userAccountPDA);
+    assert.strictEqual(userTokenAccount.available.toString(), amount.sub(part).toString());
+    assert.strictEqual((await getPDABalance(provider.connection, mint2022, program2022PDA, spl.TOKEN_2022_PROGRAM_ID)).toString(), amount.sub(part).toString());
+    assert.strictEqual(
+      (await getTokenAccountBalance(provider.connection, mint2022, payerKeypair.publicKey, spl.TOKEN_2022_PROGRAM_ID)).toString(),
+      (BigInt(10_000_000) - BigInt(amount.sub(part).toString())).toString()
+    );
+  });
+
+  it("Deposit of a transfer fee mint credits the net amount", async () => {
+    // 1% transfer fee
+    const { mint: feeMint, programTokenPDA: feeMintVault } =
+      await setupToken2022Mint(provider, program, payerKeypair, BigInt(10_000_000), 100);
+    const amount = new anchor.BN(1_000_000);
+    const net = amount.sub(amount.divn(100));
+
+    const { userTokenAccountInfo, depositEvent } = await depositWithMessage(
+      provider, program, payerKeypair, signerKeypair, feeMint, uuid(), uuid(), amount, new anchor.BN(0), expiredAt, null, spl.TOKEN_2022_PROGRAM_ID
+    );
+    assert.strictEqual(depositEvent.amount.toString(), amount.toString());
+    assert.strictEqual(depositEvent.received.toString(), net.toString());
+    assert.strictEqual(userTokenAccountInfo.available.toString(), net.toString());
+    assert.strictEqual((await getPDABalance(provider.connection, feeMint, feeMintVault, spl.TOKEN_2022_PROGRAM_ID)).toString(), net.toString());
+  });
+
+  it("Close records once expired and retained", async () => {
+    const account = uuid();
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), account, amount, new anchor.BN(0), expiredAt);
+
+    const sn = uuid();
+    const now = await getChainTime(provider);
+    const shortExpiry = new anchor.BN(now + 10);
+    await freeze(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, sn, account, amount.divn(2), shortExpiry);
+    const [recordPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("record"), bytes32Buffer(sn)],
+      program.programId
+    );
+    const closeRecord = (authority: Keypair) => program.methods
+      .closeRecords()
+      .accounts({
+        paymentState: paymentStatePDA,
+        authority: authority.publicKey,
+        payer: payerKeypair.publicKey,
+      })
+      .remainingAccounts([{ pubkey: recordPDA, isSigner: false, isWritable: true }])
+      .signers([authority])
+      .rpc();
+    const setRetention = (retention: number) => program.methods
+      .setRecordRetention(new anchor.BN(retention))
+      .accounts({ paymentState: paymentStatePDA, owner: payerKeypair.publicKey })
+      .signers([payerKeypair])
+      .rpc();
+    // The signed message has not expired, the sn could still be replayed
+    await expectError(closeRecord(payerKeypair), "RecordNotExpired");
+
+    // Expired, but still inside the retention window
+    await setRetention(3600);
+    await waitForChainTime(provider, now + 10);
+    await expectError(closeRecord(payerKeypair), "RecordNotExpired");
+
+    // Only the payer of the record or the owner may close it
+    await setRetention(0);
+    await expectError(closeRecord(user2Keypair), "Unauthorized");
+
+    await closeRecord(payerKeypair);
+    assert.strictEqual(await provider.connection.getAccountInfo(recordPDA), null, "Record should be closed");
+
+    // The closed sn cannot run again, its message has expired
+    try {
+      await freeze(provider, program, payerKeypair, signerKeypair, spl.NATIVE_MINT, sn, account, amount.divn(2), shortExpiry);
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "Expired");
+    }
+  });
+
+  it("Owner closes a record of the legacy layout", async () => {
+    // Loaded by the test validator in the layout from before expiry and payer tracking, see Anchor.toml
+    const [legacyPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("record"), bytes32Buffer('c105ed')],
+      program.programId
+    );
+    const legacyInfo = await provider.connection.getAccountInfo(legacyPDA);
+    assert.strictEqual(legacyInfo.data.length, 8 + 1, "Fixture should have the legacy layout");
+    const closeLegacy = (authority: Keypair, payer: PublicKey) => program.methods
+      .closeRecords()
+      .accounts({
+        paymentState: paymentStatePDA,
+        authority: authority.publicKey,
+        payer,
+      })
+      .remainingAccounts([{ pubkey: legacyPDA, isSigner: false, isWritable: true }])
+      .signers([authority])
+      .rpc();
+
+    // The legacy layout does not record its payer, so only the owner may close it
+    await expectError(closeLegacy(user2Keypair, user2Keypair.publicKey), "Unauthorized");
+
+    const treasury = Keypair.generate().publicKey;
+    await closeLegacy(payerKeypair, treasury);
+    assert.strictEqual(await provider.connection.getAccountInfo(legacyPDA), null, "Record should be closed");
+    assert.strictEqual(await provider.connection.getBalance(treasury), legacyInfo.lamports, "Rent should go to the designated account");
+  });
+
+  it("Freeze and Unfreeze for SOL", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 10); // 0.1 SOL
+    const fee = amount.div(new anchor.BN(10)); // 0.01 SOL
+    const account = String(SOL_DEPOSIT_ACCOUNT_FILL);
+    // Record balances before freeze
+    const programBalanceBefore = await provider.connection.getBalance(programSolPDA);
+    console.log("Program SOL Balance before freeze:", programBalanceBefore / LAMPORTS_PER_SOL);
+    let sn = uuid();
+    try {
+      await freeze(
+        provider,
+        program,
         payerKeypair,
         signerKeypair,
         spl.NATIVE_MINT,
@@ -975,6 +1788,150 @@ describe("payment", () => {
     }
   });
   
+  it("Hold is not releasable before release_at", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const holdId = bytes32Buffer(uuid());
+    const releaseAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    await freeze(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      uuid(),
+      String(SOL_DEPOSIT_ACCOUNT_FILL),
+      amount,
+      expiredAt,
+      holdId,
+      bytes32Buffer(String(USER2_TOKEN_ACCOUNT_FILL)),
+      releaseAt
+    );
+
+    const [holdPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("hold"), holdId],
+      program.programId
+    );
+    const hold = await program.account.hold.fetch(holdPDA);
+    assert.strictEqual(hold.amount.toString(), amount.toString(), "hold amount doesn't match");
+
+    try {
+      await program.methods
+        .releaseHold(holdId)
+        .accounts({
+          payer: payerKeypair.publicKey,
+          user: payerKeypair.publicKey,
+        })
+        .signers([payerKeypair])
+        .rpc();
+      assert.fail("Releasing a hold early should fail");
+    } catch (e: any) {
+      assert.strictEqual(e.error.errorCode.code, "HoldNotReleasable");
+    }
+  });
+
+  it("Named hold is drawn by unfreeze and closed once empty", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const holdId = bytes32Buffer(uuid());
+    const releaseAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    await freeze(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      uuid(),
+      String(SOL_DEPOSIT_ACCOUNT_FILL),
+      amount,
+      expiredAt,
+      holdId,
+      bytes32Buffer(String(USER2_TOKEN_ACCOUNT_FILL)),
+      releaseAt
+    );
+
+    const [holdPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("hold"), holdId],
+      program.programId
+    );
+
+    // Unfreezing through the hold empties it, after which it can be closed early
+    await unfreeze(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      uuid(),
+      String(SOL_DEPOSIT_ACCOUNT_FILL),
+      amount,
+      new anchor.BN(0),
+      expiredAt,
+      holdId
+    );
+    const hold = await program.account.hold.fetch(holdPDA);
+    assert.strictEqual(hold.amount.toString(), "0", "hold should be empty");
+
+    await program.methods
+      .releaseHold(holdId)
+      .accounts({
+        payer: payerKeypair.publicKey,
+        user: payerKeypair.publicKey,
+      })
+      .signers([payerKeypair])
+      .rpc();
+    assert.strictEqual(await provider.connection.getAccountInfo(holdPDA), null, "hold should be closed");
+  });
+
+  it("Hold is released to available after release_at", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const holdId = bytes32Buffer(uuid());
+    const account = String(SOL_DEPOSIT_ACCOUNT_FILL);
+    const [userAccountPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), bytes32Buffer(account), spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const [holdPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("hold"), holdId],
+      program.programId
+    );
+    const before = await program.account.userTokenAccount.fetch(userAccountPDA);
+
+    const releaseAt = (await getChainTime(provider)) + 2;
+    await freeze(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      uuid(),
+      account,
+      amount,
+      expiredAt,
+      holdId,
+      bytes32Buffer(String(USER2_TOKEN_ACCOUNT_FILL)),
+      new anchor.BN(releaseAt)
+    );
+    const frozen = await program.account.userTokenAccount.fetch(userAccountPDA);
+    assert.strictEqual(frozen.available.toString(), before.available.sub(amount).toString());
+    assert.strictEqual(frozen.held.toString(), before.held.add(amount).toString());
+
+    // Anyone may release once the time lock is over, the rent goes back to the payer
+    await waitForChainTime(provider, releaseAt);
+    await program.methods
+      .releaseHold(holdId)
+      .accounts({
+        payer: payerKeypair.publicKey,
+        user: user2Keypair.publicKey,
+      })
+      .signers([user2Keypair])
+      .rpc();
+
+    const after = await program.account.userTokenAccount.fetch(userAccountPDA);
+    assert.strictEqual(after.available.toString(), before.available.toString(), "available should be restored");
+    assert.strictEqual(after.frozen.toString(), before.frozen.toString(), "frozen should be restored");
+    assert.strictEqual(after.held.toString(), before.held.toString(), "held should be restored");
+    assert.strictEqual(await provider.connection.getAccountInfo(holdPDA), null, "hold should be closed");
+  });
+
   it("Freeze and Unfreeze for Token", async () => {
     const amount = new anchor.BN(LAMPORTS_PER_SOL / 10); // 0.1 SOL
     const fee = amount.div(new anchor.BN(10)); // 0.01 SOL
@@ -1159,6 +2116,40 @@ describe("payment", () => {
     }
   });
 
+  it("Settle pays SOL out to a wSOL account", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const wsolAccount = spl.getAssociatedTokenAddressSync(spl.NATIVE_MINT, payerKeypair.publicKey);
+    const wsolBefore = (await spl.getAccount(provider.connection, wsolAccount)).amount;
+    const deal = new SettlementData(
+      bytes32Buffer(String(SOL_DEPOSIT_ACCOUNT_FILL)),
+      bytes32Buffer(String(USER2_SOL_ACCOUNT_FILL)),
+      amount.add(fee),
+      new anchor.BN(0),
+      amount,
+      fee,
+      new anchor.BN(0),
+      new anchor.BN(0)
+    );
+    await settleWithAccount(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      wsolAccount,
+      feeToKeypair.publicKey,
+      uuid(),
+      deal,
+      expiredAt,
+      undefined,
+      undefined,
+      undefined,
+    );
+    // The lamports are synced into the token amount
+    assert.strictEqual((await spl.getAccount(provider.connection, wsolAccount)).amount, wsolBefore + BigInt(amount.toString()));
+  });
+
   it("Settle for Token", async () => {
     const amount = new anchor.BN(LAMPORTS_PER_SOL / 100); // 0.1 SOL
     const fee = amount.div(new anchor.BN(10)); // 0.01 SOL
@@ -1193,4 +2184,736 @@ describe("payment", () => {
     }
   });
   
+  it("Settle pays a hold into its beneficiary's ledger only", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const from = String(SOL_DEPOSIT_ACCOUNT_FILL);
+    const to = String(USER2_SOL_ACCOUNT_FILL);
+    const holdId = bytes32Buffer(uuid());
+    const releaseAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    await freeze(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      uuid(),
+      from,
+      amount.add(fee).muln(2),
+      expiredAt,
+      holdId,
+      bytes32Buffer(to),
+      releaseAt
+    );
+
+    const deal = new SettlementData(
+      bytes32Buffer(from),
+      bytes32Buffer(to),
+      new anchor.BN(0),
+      amount.add(fee),
+      amount,
+      fee,
+      amount.add(fee),
+      new anchor.BN(0),
+      holdId
+    );
+    // The held part may not leave the ledgers, even toward the beneficiary's own wallet
+    await expectError(settleWithAccount(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      user2Keypair.publicKey,
+      feeToKeypair.publicKey,
+      uuid(),
+      deal,
+      expiredAt,
+      undefined,
+      undefined,
+      undefined,
+    ), "InvalidParameter");
+
+    await settle(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      ZERO_ACCOUNT,
+      feeToKeypair.publicKey,
+      uuid(),
+      deal,
+      expiredAt,
+    );
+
+    const [holdPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("hold"), holdId],
+      program.programId
+    );
+    const hold = await program.account.hold.fetch(holdPDA);
+    assert.strictEqual(hold.amount.toString(), amount.add(fee).toString(), "hold should be drawn by the paid amount");
+  });
+
+  it("Settle rejects a mismatched or overdrawn hold", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const from = String(SOL_DEPOSIT_ACCOUNT_FILL);
+    const to = String(USER2_SOL_ACCOUNT_FILL);
+    const releaseAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    const holdFor = async (account: string, beneficiary: string, holdAmount: anchor.BN) => {
+      const holdId = bytes32Buffer(uuid());
+      await freeze(
+        provider,
+        program,
+        payerKeypair,
+        signerKeypair,
+        spl.NATIVE_MINT,
+        uuid(),
+        account,
+        holdAmount,
+        expiredAt,
+        holdId,
+        bytes32Buffer(beneficiary),
+        releaseAt
+      );
+      return holdId;
+    };
+    const settleFrom = (holdId: Buffer, payee: string, paid: anchor.BN) => settleWithAccount(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      ZERO_ACCOUNT,
+      feeToKeypair.publicKey,
+      uuid(),
+      new SettlementData(
+        bytes32Buffer(from),
+        bytes32Buffer(payee),
+        new anchor.BN(0),
+        paid,
+        paid.sub(fee),
+        fee,
+        paid,
+        new anchor.BN(0),
+        holdId
+      ),
+      expiredAt,
+      undefined,
+      undefined,
+      undefined,
+    );
+
+    // A hold reserved on another account cannot fund this one's settlement
+    const otherHold = await holdFor(to, to, amount.add(fee));
+    await expectError(settleFrom(otherHold, to, amount.add(fee)), "InvalidParameter");
+
+    // A hold only pays out to its beneficiary
+    const ownHold = await holdFor(from, to, amount.add(fee));
+    await expectError(settleFrom(ownHold, String(USER2_TOKEN_ACCOUNT_FILL), amount.add(fee)), "InvalidParameter");
+
+    // A deal cannot draw more than the hold reserves
+    await expectError(settleFrom(ownHold, to, amount.add(fee).muln(2)), "InsufficientFrozen");
+  });
+
+  it("Settle and transfer reject a ledger loaded twice", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const from = String(SOL_DEPOSIT_ACCOUNT_FILL);
+    const settleTo = (to: string) => settleWithAccount(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      ZERO_ACCOUNT,
+      feeToKeypair.publicKey,
+      uuid(),
+      new SettlementData(
+        bytes32Buffer(from),
+        bytes32Buffer(to),
+        amount.add(fee),
+        new anchor.BN(0),
+        amount,
+        fee,
+        new anchor.BN(0),
+        new anchor.BN(0)
+      ),
+      expiredAt,
+      undefined,
+      undefined,
+      undefined,
+    );
+    const transferTo = (to: string) => transferWitchAccount(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      ZERO_ACCOUNT,
+      feeToKeypair.publicKey,
+      uuid(),
+      from,
+      to,
+      amount,
+      fee,
+      expiredAt,
+      undefined,
+      undefined,
+      undefined,
+    );
+
+    // Two copies of one ledger would overwrite each other and desync the mint totals
+    await expectError(settleTo(from), "InvalidParameter");
+    await expectError(settleTo(FEE_ACCOUNT_FILL), "InvalidParameter");
+    await expectError(transferTo(from), "InvalidParameter");
+    await expectError(transferTo(FEE_ACCOUNT_FILL), "InvalidParameter");
+  });
+
+  it("Split settlement for SOL", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const from = bytes32Buffer(String(SOL_DEPOSIT_ACCOUNT_FILL));
+    const payees = [
+      new SplitPayee(bytes32Buffer(String(USER2_TOKEN_ACCOUNT_FILL)), ZERO_ACCOUNT, amount),
+      new SplitPayee(bytes32Buffer(uuid()), ZERO_ACCOUNT, amount.divn(2)),
+    ];
+    const deal = new SplitSettlementData(
+      from,
+      amount.add(amount.divn(2)).add(fee),
+      new anchor.BN(0),
+      fee,
+      payees
+    );
+    await settleSplit(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), deal, expiredAt);
+
+    const [newPayeePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), payees[1].account, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const newPayee = await program.account.userTokenAccount.fetch(newPayeePDA);
+    assert.strictEqual(newPayee.available.toString(), payees[1].amount.toString(), "new payee should be created and credited");
+  });
+
+  it("Split settlement pays out an external leg", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const from = bytes32Buffer(String(SOL_DEPOSIT_ACCOUNT_FILL));
+    const payees = [
+      new SplitPayee(bytes32Buffer(String(USER2_TOKEN_ACCOUNT_FILL)), ZERO_ACCOUNT, amount),
+      new SplitPayee(Buffer.alloc(32), user2Keypair.publicKey, amount.divn(2)),
+    ];
+    const deal = new SplitSettlementData(
+      from,
+      amount.add(amount.divn(2)).add(fee),
+      new anchor.BN(0),
+      fee,
+      payees
+    );
+    const outBalanceBefore = await provider.connection.getBalance(user2Keypair.publicKey);
+    await settleSplit(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), deal, expiredAt);
+    const outBalanceAfter = await provider.connection.getBalance(user2Keypair.publicKey);
+    assert.strictEqual(outBalanceAfter - outBalanceBefore, payees[1].amount.toNumber(), "out leg should be paid directly");
+  });
+
+  it("Split settlement rejects duplicate payees and mismatched accounts", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const from = bytes32Buffer(String(SOL_DEPOSIT_ACCOUNT_FILL));
+    const payee = bytes32Buffer(String(USER2_TOKEN_ACCOUNT_FILL));
+    // The same ledger twice would let the last write win
+    const duplicate = new SplitSettlementData(from, amount.muln(2), new anchor.BN(0), new anchor.BN(0), [
+      new SplitPayee(payee, ZERO_ACCOUNT, amount),
+      new SplitPayee(payee, ZERO_ACCOUNT, amount),
+    ]);
+    await expectError(
+      settleSplit(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), duplicate, expiredAt),
+      "InvalidPayees"
+    );
+
+    const deal = new SplitSettlementData(from, amount.muln(2), new anchor.BN(0), new anchor.BN(0), [
+      new SplitPayee(payee, ZERO_ACCOUNT, amount),
+      new SplitPayee(Buffer.alloc(32), user2Keypair.publicKey, amount),
+    ]);
+    const [payeePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), payee, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+
+    // remaining_accounts must hold exactly one account per payee
+    await expectError(
+      settleSplit(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), deal, expiredAt, [payeePDA]),
+      "InvalidPayees"
+    );
+
+    // and an external leg must be paid to its own `out`
+    await expectError(
+      settleSplit(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), deal, expiredAt, [payeePDA, feeToKeypair.publicKey]),
+      "InvalidPayees"
+    );
+  });
+
+  it("Split settlement pays a hold's frozen part to its beneficiary", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const account = String(SOL_DEPOSIT_ACCOUNT_FILL);
+    const beneficiary = bytes32Buffer(String(USER2_TOKEN_ACCOUNT_FILL));
+    const holdId = bytes32Buffer(uuid());
+    await freeze(
+      provider,
+      program,
+      payerKeypair,
+      signerKeypair,
+      spl.NATIVE_MINT,
+      uuid(),
+      account,
+      amount,
+      expiredAt,
+      holdId,
+      beneficiary,
+      new anchor.BN(Math.floor(Date.now() / 1000) + 3600)
+    );
+
+    // The beneficiary merely appearing as a leg is not enough, it must receive the frozen part
+    const misrouted = new SplitSettlementData(bytes32Buffer(account), amount.divn(2), amount, new anchor.BN(0), [
+      new SplitPayee(beneficiary, ZERO_ACCOUNT, amount.divn(2)),
+      new SplitPayee(bytes32Buffer(uuid()), ZERO_ACCOUNT, amount),
+    ], holdId);
+    await expectError(
+      settleSplit(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), misrouted, expiredAt),
+      "InvalidParameter"
+    );
+
+    const deal = new SplitSettlementData(bytes32Buffer(account), amount.divn(2), amount, new anchor.BN(0), [
+      new SplitPayee(beneficiary, ZERO_ACCOUNT, amount),
+      new SplitPayee(bytes32Buffer(uuid()), ZERO_ACCOUNT, amount.divn(2)),
+    ], holdId);
+    await settleSplit(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), deal, expiredAt);
+
+    const [holdPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("hold"), holdId],
+      program.programId
+    );
+    const hold = await program.account.hold.fetch(holdPDA);
+    assert.strictEqual(hold.amount.toString(), "0", "hold should be drawn by the frozen part");
+  });
+
+  it("Batch settlement for SOL", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const zero = new anchor.BN(0);
+    const from = bytes32Buffer(String(SOL_DEPOSIT_ACCOUNT_FILL));
+    const middle = bytes32Buffer(uuid());
+    const last = bytes32Buffer(uuid());
+    // The second deal spends what the first one credited
+    const deals = [
+      new SettlementData(from, middle, amount.add(fee), zero, amount, fee, zero, zero),
+      new SettlementData(middle, last, amount, zero, amount.sub(fee), fee, zero, zero),
+    ];
+    await settleBatch(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), deals, expiredAt);
+
+    const [middlePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), middle, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const [lastPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), last, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    assert.strictEqual((await program.account.userTokenAccount.fetch(middlePDA)).available.toNumber(), 0);
+    assert.strictEqual((await program.account.userTokenAccount.fetch(lastPDA)).available.toString(), amount.sub(fee).toString());
+  });
+
+  it("Batch settlement rejects malformed batches", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const zero = new anchor.BN(0);
+    const from = bytes32Buffer(String(SOL_DEPOSIT_ACCOUNT_FILL));
+    const to = bytes32Buffer(uuid());
+    const dealTo = (account: Buffer) => new SettlementData(from, account, amount, zero, amount, zero, zero, zero);
+    const settle = (deals: SettlementData[], sn = uuid(), ledgerAccounts: PublicKey[] | undefined = undefined) =>
+      settleBatch(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, sn, deals, expiredAt, ledgerAccounts);
+
+    // Holds are only released through settle
+    const held = new SettlementData(from, to, zero, amount, amount, zero, amount, zero, bytes32Buffer(uuid()));
+    await expectError(settle([held]), "InvalidParameter");
+    // Neither the fee ledger nor the same ledger on both sides may be loaded
+    await expectError(settle([dealTo(bytes32Buffer(FEE_ACCOUNT_FILL))]), "InvalidBatch");
+    await expectError(settle([dealTo(from)]), "InvalidBatch");
+    // An empty batch, and one past MAX_BATCH_DEALS, which does not even fit in a transaction
+    await expectError(settle([]), "InvalidBatch");
+    await assert.rejects(settle(Array(65).fill(dealTo(to))), (error) => !(error instanceof anchor.AnchorError));
+
+    // remaining_accounts must hold one ledger per distinct account, in order of first appearance
+    const [fromPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), from, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const [toPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), to, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    await expectError(settle([dealTo(to)], uuid(), [fromPDA]), "InvalidBatch");
+    await expectError(settle([dealTo(to)], uuid(), [toPDA, fromPDA]), "InvalidUserTokenAccount");
+
+    // A record is only consumed once
+    const sn = uuid();
+    await settle([dealTo(to)], sn);
+    await expectError(settle([dealTo(to)], sn), "AlreadyExecuted");
+    assert.strictEqual((await program.account.userTokenAccount.fetch(toPDA)).available.toString(), amount.toString());
+  });
+
+  it("Transfer many for SOL", async () => {
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 1000);
+    const fee = amount.divn(10);
+    const from = bytes32Buffer(String(SOL_DEPOSIT_ACCOUNT_FILL));
+    const recipients = [1, 2, 3].map((n) => new TransferRecipient(bytes32Buffer(uuid()), amount.muln(n)));
+    const [fromPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), from, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const fromBefore = await program.account.userTokenAccount.fetch(fromPDA);
+
+    await transferMany(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), from, recipients, fee, expiredAt);
+
+    const fromAfter = await program.account.userTokenAccount.fetch(fromPDA);
+    assert.strictEqual(fromBefore.available.sub(fromAfter.available).toString(), amount.muln(6).add(fee).toString());
+    for (const recipient of recipients) {
+      const [toPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user-token"), recipient.to, spl.NATIVE_MINT.toBuffer()],
+        program.programId
+      );
+      assert.strictEqual((await program.account.userTokenAccount.fetch(toPDA)).available.toString(), recipient.amount.toString());
+    }
+
+    // A duplicate recipient fails the whole fan-out
+    const duplicate = [recipients[0], recipients[0]];
+    try {
+      await transferMany(program, payerKeypair, signerKeypair, spl.NATIVE_MINT, uuid(), from, duplicate, fee, expiredAt);
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "InvalidRecipients");
+    }
+    const fromUnchanged = await program.account.userTokenAccount.fetch(fromPDA);
+    assert.strictEqual(fromUnchanged.available.toString(), fromAfter.available.toString());
+  });
+
+  it("Claim fees for SOL", async () => {
+    const feeSolAccountBefore = await program.account.userTokenAccount.fetch(feeSolPDA);
+    const amount = feeSolAccountBefore.available;
+    assert(amount.gtn(0), "Fee ledger should have accrued fees");
+    const balanceBefore = await provider.connection.getBalance(feeToKeypair.publicKey);
+
+    await program.methods
+      .claimFees(amount)
+      .accounts({
+        paymentState: paymentStatePDA,
+        feeTokenAccount: feeSolPDA,
+        feeTo: feeToKeypair.publicKey,
+        mint: spl.NATIVE_MINT,
+        to: feeToKeypair.publicKey,
+        programToken: programSolPDA,
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([feeToKeypair])
+      .rpc();
+
+    const feeSolAccountAfter = await program.account.userTokenAccount.fetch(feeSolPDA);
+    assert.strictEqual(feeSolAccountAfter.available.toNumber(), 0, "Fee ledger should be empty");
+    const balanceAfter = await provider.connection.getBalance(feeToKeypair.publicKey);
+    assert(balanceAfter > balanceBefore, "fee_to balance should increase");
+  });
+
+  it("Check solvency", async () => {
+    for (const [tokenMint, tokenProgram] of [[spl.NATIVE_MINT, spl.TOKEN_PROGRAM_ID], [mint, spl.TOKEN_PROGRAM_ID]]) {
+      const [mintTotalsPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-totals"), tokenMint.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .checkSolvency()
+        .accounts({
+          mint: tokenMint,
+          tokenProgram,
+        })
+        .rpc();
+      const totals = await program.account.mintTotals.fetch(mintTotalsPDA);
+      assert(totals.mint.equals(tokenMint), "Totals should belong to the mint");
+      if (tokenMint.equals(spl.NATIVE_MINT)) {
+        const vaultBalance = await provider.connection.getBalance(programSolPDA);
+        assert(totals.rentReserve.gtn(0), "SOL vault should record its rent reserve");
+        assert(vaultBalance >= totals.rentReserve.toNumber(), "SOL vault should stay rent-exempt");
+      }
+    }
+  });
+
+  it("Sweep SOL surplus", async () => {
+    const donation = LAMPORTS_PER_SOL / 100;
+    await provider.sendAndConfirm(new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: payerKeypair.publicKey,
+        toPubkey: programSolPDA,
+        lamports: donation,
+      })
+    ), [payerKeypair]);
+
+    const treasury = Keypair.generate().publicKey;
+    await program.methods
+      .sweepSurplus(new anchor.BN(donation))
+      .accounts({
+        owner: payerKeypair.publicKey,
+        mint: spl.NATIVE_MINT,
+        to: treasury,
+        tokenProgram: spl.TOKEN_PROGRAM_ID,
+      })
+      .signers([payerKeypair])
+      .rpc();
+    assert.strictEqual(await provider.connection.getBalance(treasury), donation);
+
+    try {
+      await program.methods
+        .sweepSurplus(new anchor.BN(donation))
+        .accounts({
+          owner: payerKeypair.publicKey,
+          mint: spl.NATIVE_MINT,
+          to: treasury,
+          tokenProgram: spl.TOKEN_PROGRAM_ID,
+        })
+        .signers([payerKeypair])
+        .rpc();
+      assert.fail("Sweeping ledger-backed funds should fail");
+    } catch (e: any) {
+      assert(e.message.includes("InsufficientSurplus"), e.message);
+    }
+  });
+
+  it("Exit requires a bound wallet", async () => {
+    await program.methods
+      .setExitDelay(new anchor.BN(60))
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+      })
+      .signers([payerKeypair])
+      .rpc();
+
+    try {
+      await program.methods
+        .requestExit(bytes32Buffer(String(TOKEN_DEPOSIT_ACCOUNT_FILL)))
+        .accounts({
+          wallet: payerKeypair.publicKey,
+          mint: spl.NATIVE_MINT,
+        })
+        .signers([payerKeypair])
+        .rpc();
+      assert.fail("Exit without a bound wallet should fail");
+    } catch (e: any) {
+      assert(e.message.includes("WalletNotBound"), e.message);
+    }
+
+    await program.methods
+      .setExitDelay(new anchor.BN(0))
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+      })
+      .signers([payerKeypair])
+      .rpc();
+  });
+
+  it("Backend cancels an exit and a later one executes", async () => {
+    const config = { paymentState: paymentStatePDA, owner: payerKeypair.publicKey };
+    for (const delay of [-1, 59, 30 * 24 * 60 * 60 + 1]) {
+      await expectError(
+        program.methods.setExitDelay(new anchor.BN(delay)).accounts(config).signers([payerKeypair]).rpc(),
+        "InvalidParameter"
+      );
+    }
+
+    const walletKeypair = Keypair.generate();
+    const account = uuid();
+    const accountBuffer = bytes32Buffer(account);
+    const amount = new anchor.BN(LAMPORTS_PER_SOL / 100);
+    await depositSol(provider, program, payerKeypair, signerKeypair, uuid(), account, amount, new anchor.BN(0), expiredAt);
+    const [userAccountPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), accountBuffer, spl.NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const recordPDA = (snBuffer: Buffer) => PublicKey.findProgramAddressSync([Buffer.from("record"), snBuffer], program.programId)[0];
+
+    let snBuffer = bytes32Buffer(uuid());
+    let message = buildMessage(program.programId, Operation.BindWallet, [
+      snBuffer, accountBuffer, walletKeypair.publicKey.toBuffer(), expiredAt.toArrayLike(Buffer, 'le', 8),
+      payerKeypair.publicKey.toBuffer(), spl.NATIVE_MINT.toBuffer(),
+    ]);
+    let signed = getEd25519Instruction(message, signerKeypair);
+    await program.methods
+      .bindWallet(snBuffer, accountBuffer, walletKeypair.publicKey, expiredAt, signed.signature)
+      .accounts({
+        paymentState: paymentStatePDA,
+        userTokenAccount: userAccountPDA,
+        user: payerKeypair.publicKey,
+        mint: spl.NATIVE_MINT,
+        record: recordPDA(snBuffer),
+        systemProgram: SystemProgram.programId,
+        instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .preInstructions([signed.ed25519Instruction])
+      .signers([payerKeypair])
+      .rpc();
+
+    const exitDelay = 60;
+    await program.methods.setExitDelay(new anchor.BN(exitDelay)).accounts(config).signers([payerKeypair]).rpc();
+    const requestExit = () => program.methods
+      .requestExit(accountBuffer)
+      .accounts({ wallet: walletKeypair.publicKey, mint: spl.NATIVE_MINT })
+      .signers([walletKeypair])
+      .rpc();
+    const executeExit = () => program.methods
+      .executeExit(accountBuffer)
+      .accounts({ wallet: walletKeypair.publicKey, mint: spl.NATIVE_MINT, to: walletKeypair.publicKey, tokenProgram: spl.TOKEN_PROGRAM_ID })
+      .signers([walletKeypair])
+      .rpc();
+    const cancelExit = () => {
+      const snBuffer = bytes32Buffer(uuid());
+      const message = buildMessage(program.programId, Operation.CancelExit, [
+        snBuffer, accountBuffer, expiredAt.toArrayLike(Buffer, 'le', 8),
+        payerKeypair.publicKey.toBuffer(), spl.NATIVE_MINT.toBuffer(),
+      ]);
+      const {ed25519Instruction, signature} = getEd25519Instruction(message, signerKeypair);
+      return program.methods
+        .cancelExit(snBuffer, accountBuffer, expiredAt, signature)
+        .accounts({
+          paymentState: paymentStatePDA,
+          userTokenAccount: userAccountPDA,
+          user: payerKeypair.publicKey,
+          mint: spl.NATIVE_MINT,
+          record: recordPDA(snBuffer),
+          systemProgram: SystemProgram.programId,
+          instructionSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .preInstructions([ed25519Instruction])
+        .signers([payerKeypair])
+        .rpc();
+    };
+
+    try {
+      // Vetoed by the backend
+      await requestExit();
+      await cancelExit();
+      let userTokenAccount = await program.account.userTokenAccount.fetch(userAccountPDA);
+      assert.strictEqual(userTokenAccount.exitRequestedAt.toNumber(), 0);
+      await expectError(executeExit(), "ExitNotRequested");
+      await expectError(cancelExit(), "ExitNotRequested");
+
+      // A new request runs its own delay and then pays out to the wallet
+      await requestExit();
+      await expectError(executeExit(), "ExitNotReady");
+      userTokenAccount = await program.account.userTokenAccount.fetch(userAccountPDA);
+      await waitForChainTime(provider, userTokenAccount.exitRequestedAt.toNumber() + exitDelay);
+      await executeExit();
+      userTokenAccount = await program.account.userTokenAccount.fetch(userAccountPDA);
+      assert.strictEqual(userTokenAccount.available.toString(), "0");
+      assert.strictEqual(userTokenAccount.exitRequestedAt.toNumber(), 0);
+      assert.strictEqual(await provider.connection.getBalance(walletKeypair.publicKey), amount.toNumber());
+    } finally {
+      await program.methods.setExitDelay(new anchor.BN(0)).accounts(config).signers([payerKeypair]).rpc();
+    }
+  });
+
+  it("Change fee account and migrate fee ledgers", async () => {
+    const oldFeeAccount = bytes32Buffer(FEE_ACCOUNT_FILL);
+    const newFeeAccount = bytes32Buffer(uuid());
+    const feePDA = (feeAccount: Buffer, tokenMint: PublicKey) => PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), feeAccount, tokenMint.toBuffer()],
+      program.programId
+    )[0];
+    const newFeeSolPDA = feePDA(newFeeAccount, spl.NATIVE_MINT);
+    const newFeeTokenPDA = feePDA(newFeeAccount, mint);
+    const migration = (tokenMint: PublicKey, from: PublicKey, to: PublicKey) => [
+      { pubkey: tokenMint, isSigner: false, isWritable: false },
+      { pubkey: from, isSigner: false, isWritable: true },
+      { pubkey: to, isSigner: false, isWritable: true },
+    ];
+    const solBefore = await program.account.userTokenAccount.fetch(feeSolPDA);
+    const tokenBefore = await program.account.userTokenAccount.fetch(feeTokenPDA);
+
+    // The SOL fee ledger moves with the change, the token one is left for later
+    await program.methods
+      .changeFeeToAccount(newFeeAccount)
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(migration(spl.NATIVE_MINT, feeSolPDA, newFeeSolPDA))
+      .signers([payerKeypair])
+      .rpc();
+
+    let paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.deepStrictEqual(paymentStateAccount.previousFeeToAccount, bufferToArray(oldFeeAccount));
+    assert.deepStrictEqual(paymentStateAccount.feeToAccount, bufferToArray(newFeeAccount));
+    assert.strictEqual((await program.account.userTokenAccount.fetch(feeSolPDA)).available.toNumber(), 0);
+    assert.strictEqual((await program.account.userTokenAccount.fetch(newFeeSolPDA)).available.toString(), solBefore.available.toString());
+
+    // Fees on the unmigrated mint still work, the new fee ledger is created on demand
+    const amount = new anchor.BN(1000);
+    const fee = new anchor.BN(100);
+    await freeze(provider, program, payerKeypair, signerKeypair, mint, uuid(), TOKEN_DEPOSIT_ACCOUNT_FILL, amount, expiredAt);
+    await unfreezeWithAccount(provider, program, payerKeypair, signerKeypair, mint, uuid(), TOKEN_DEPOSIT_ACCOUNT_FILL, amount, fee, expiredAt, undefined, newFeeTokenPDA);
+    assert.strictEqual((await program.account.userTokenAccount.fetch(newFeeTokenPDA)).available.toString(), fee.toString());
+
+    const migrateFeeAccount = (oldFeeTokenAccount: PublicKey) => program.methods
+      .migrateFeeAccount()
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+        mint: mint,
+        oldFeeTokenAccount,
+        feeTokenAccount: newFeeTokenPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([payerKeypair])
+      .rpc();
+
+    // Only the previous fee ledger can be migrated, never a user's ledger
+    const [userTokenPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user-token"), bytes32Buffer(TOKEN_DEPOSIT_ACCOUNT_FILL), mint.toBuffer()],
+      program.programId
+    );
+    try {
+      await migrateFeeAccount(userTokenPDA);
+      assert.fail("Expected an error but the transaction succeeded");
+    } catch (error) {
+      assert.ok(error instanceof anchor.AnchorError);
+      assert.strictEqual(error.error.errorCode.code, "ConstraintSeeds");
+    }
+
+    await migrateFeeAccount(feeTokenPDA);
+    assert.strictEqual((await program.account.userTokenAccount.fetch(feeTokenPDA)).available.toNumber(), 0);
+    assert.strictEqual((await program.account.userTokenAccount.fetch(newFeeTokenPDA)).available.toString(), tokenBefore.available.add(fee).toString());
+
+    // Switch back, migrating both mints in the same instruction
+    await program.methods
+      .changeFeeToAccount(oldFeeAccount)
+      .accounts({
+        paymentState: paymentStatePDA,
+        owner: payerKeypair.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([
+        ...migration(spl.NATIVE_MINT, newFeeSolPDA, feeSolPDA),
+        ...migration(mint, newFeeTokenPDA, feeTokenPDA),
+      ])
+      .signers([payerKeypair])
+      .rpc();
+
+    paymentStateAccount = await program.account.paymentState.fetch(paymentStatePDA);
+    assert.deepStrictEqual(paymentStateAccount.feeToAccount, bufferToArray(oldFeeAccount));
+    assert.strictEqual((await program.account.userTokenAccount.fetch(feeSolPDA)).available.toString(), solBefore.available.toString());
+    assert.strictEqual((await program.account.userTokenAccount.fetch(feeTokenPDA)).available.toString(), tokenBefore.available.add(fee).toString());
+  });
+
 });